@@ -0,0 +1,107 @@
+//! Runs a small application against simulated switches in the terminal.
+//!
+//! `cargo run --example simulator --features std`
+//!
+//! Press `s` to toggle the start button, `b` to toggle the brake, and `q` to quit.
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::{cursor, execute, queue, terminal};
+use switch_hal::sim::Simulator;
+use switch_hal::{ActiveHigh, ActiveLow, InputSwitch, OutputSwitch, ToggleableOutputSwitch};
+
+/// Application logic, written only in terms of switches
+struct Motor<B, S, R, H> {
+    start: B,
+    brake: S,
+    running: R,
+    heartbeat: H,
+}
+
+impl<B, S, R, H> Motor<B, S, R, H>
+where
+    B: InputSwitch,
+    S: InputSwitch,
+    R: OutputSwitch,
+    H: ToggleableOutputSwitch,
+{
+    fn step(&mut self) {
+        let start = self.start.is_active().unwrap_or(false);
+        let brake = self.brake.is_active().unwrap_or(true);
+        let _ = if start && !brake {
+            self.running.on()
+        } else {
+            self.running.off()
+        };
+    }
+
+    fn beat(&mut self) {
+        let _ = self.heartbeat.toggle();
+    }
+}
+
+fn draw(sim: &Simulator, out: &mut impl Write) -> io::Result<()> {
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    let mut panel = Vec::new();
+    sim.render(&mut panel)?;
+    // raw mode does not return the cursor to the start of the line
+    let panel = String::from_utf8_lossy(&panel).replace('\n', "\r\n");
+    write!(out, "{}\r\npress q to quit\r\n", panel)?;
+    out.flush()
+}
+
+fn main() -> io::Result<()> {
+    let mut sim = Simulator::new();
+    let mut motor = Motor {
+        start: sim.button::<ActiveLow>("start", 's'),
+        brake: sim.button::<ActiveHigh>("brake", 'b'),
+        running: sim.led::<ActiveHigh>("running"),
+        heartbeat: sim.led::<ActiveLow>("heartbeat"),
+    };
+
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mut last_beat = Instant::now();
+    // errors leave the loop instead of returning, so the terminal is always restored
+    let result = loop {
+        let ready = match event::poll(Duration::from_millis(50)) {
+            Ok(ready) => ready,
+            Err(e) => break Err(e),
+        };
+        if ready {
+            let event = match event::read() {
+                Ok(event) => event,
+                Err(e) => break Err(e),
+            };
+            if let Event::Key(key) = event {
+                match key.code {
+                    KeyCode::Char('q') => break Ok(()),
+                    KeyCode::Char(c) if key.kind == KeyEventKind::Press => {
+                        sim.handle_key(c);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        motor.step();
+        if last_beat.elapsed() >= Duration::from_millis(500) {
+            motor.beat();
+            last_beat = Instant::now();
+        }
+        if let Err(e) = draw(&sim, &mut out) {
+            break Err(e);
+        }
+    };
+
+    execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
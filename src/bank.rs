@@ -0,0 +1,152 @@
+//! Banks of switches that are read and written as a single integer,
+//! such as a row of status LEDs or an 8 position DIP switch.
+//!
+//! Bit `i` of the integer corresponds to switch `i` of the bank, and a set bit means the switch is on/active,
+//! so each switch's `ActiveLevel` is respected.
+//! Banks of mixed `ActiveLevel`s can be built from trait objects.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::{IntoSwitch, OutputSwitch};
+//! use switch_hal::bank::SwitchBank;
+//! # let (p0, p1, p2) = (mock::Pin::new(), mock::Pin::new(), mock::Pin::new());
+//! let mut green = p0.into_active_high_switch();
+//! let mut yellow = p1.into_active_low_switch();
+//! let mut red = p2.into_active_high_switch();
+//!
+//! let mut leds = SwitchBank::new([
+//!     &mut green as &mut dyn OutputSwitch<Error = _>,
+//!     &mut yellow,
+//!     &mut red,
+//! ]);
+//! leds.write_bits(0b101).unwrap();
+//! ```
+use crate::{InputSwitch, OutputSwitch, SwitchState};
+
+/// Error returned by a [SwitchBank](SwitchBank), identifying the switch that failed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Error<E> {
+    /// Index of the switch that failed
+    pub index: usize,
+    /// Error returned by the switch
+    pub error: E,
+}
+
+/// A fixed size bank of switches.
+///
+/// Banks are limited to 32 switches, so they always fit in a `u32` bitmask.
+pub struct SwitchBank<B> {
+    switches: B,
+}
+
+impl<S, const N: usize> SwitchBank<[S; N]> {
+    /// Constructs a new [SwitchBank](SwitchBank), where `switches[0]` is the least significant bit
+    ///
+    /// # Panics
+    /// Panics if there are more than 32 switches.
+    pub fn new(switches: [S; N]) -> Self {
+        assert!(N <= 32, "SwitchBank supports at most 32 switches");
+        SwitchBank { switches }
+    }
+
+    /// Consumes the [SwitchBank](SwitchBank) and returns the underlying switches
+    pub fn into_inner(self) -> [S; N] {
+        self.switches
+    }
+
+    /// Returns the underlying switches
+    pub fn switches(&self) -> &[S; N] {
+        &self.switches
+    }
+
+    /// Returns the underlying switches mutably
+    pub fn switches_mut(&mut self) -> &mut [S; N] {
+        &mut self.switches
+    }
+}
+
+impl<S: OutputSwitch, const N: usize> SwitchBank<[S; N]> {
+    /// Turns on every switch whose bit is set and turns off every other switch.
+    ///
+    /// Bits beyond the size of the bank are ignored.
+    /// Switches are written in order, stopping at the first failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::IntoSwitch;
+    /// use switch_hal::bank::SwitchBank;
+    /// # let pins = [(); 8].map(|_| mock::Pin::new());
+    /// let mut leds = SwitchBank::new(pins.map(|pin| pin.into_active_low_switch()));
+    /// leds.write_bits(0b1010_0011).unwrap();
+    /// ```
+    pub fn write_bits(&mut self, bits: u32) -> Result<(), Error<S::Error>> {
+        for (index, switch) in self.switches.iter_mut().enumerate() {
+            let state = SwitchState::from(bits & (1 << index) != 0);
+            switch
+                .set_state(state)
+                .map_err(|error| Error { index, error })?;
+        }
+        Ok(())
+    }
+
+    /// Turns every switch on or off according to `states`
+    pub fn set_states(&mut self, states: [SwitchState; N]) -> Result<(), Error<S::Error>> {
+        for (index, (switch, state)) in self.switches.iter_mut().zip(states).enumerate() {
+            switch
+                .set_state(state)
+                .map_err(|error| Error { index, error })?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: InputSwitch, const N: usize> SwitchBank<[S; N]> {
+    /// Reads the bank into an integer, setting the bit of every active switch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock::{self, State};
+    /// use switch_hal::IntoSwitch;
+    /// use switch_hal::bank::SwitchBank;
+    /// # let pins = [State::Low, State::High, State::Low, State::Low].map(mock::Pin::with_state);
+    /// let dip = SwitchBank::new(pins.map(|pin| pin.into_active_low_switch()));
+    /// assert_eq!(0b1101, dip.read_bits().unwrap());
+    /// ```
+    pub fn read_bits(&self) -> Result<u32, Error<S::Error>> {
+        let mut bits = 0;
+        for (index, active) in self.states().enumerate() {
+            if bool::from(active?) {
+                bits |= 1 << index;
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Iterates over the [SwitchState](crate::SwitchState) of each switch in the bank
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock::{self, State};
+    /// use switch_hal::{IntoSwitch, SwitchState};
+    /// use switch_hal::bank::SwitchBank;
+    /// # let pins = [State::High, State::Low].map(mock::Pin::with_state);
+    /// let buttons = SwitchBank::new(pins.map(|pin| pin.into_active_high_switch()));
+    /// for (index, state) in buttons.states().enumerate() {
+    ///     if state.unwrap() == SwitchState::On {
+    ///         // button `index` is pressed
+    ///     }
+    /// }
+    /// ```
+    pub fn states(&self) -> impl Iterator<Item = Result<SwitchState, Error<S::Error>>> + '_ {
+        self.switches
+            .iter()
+            .enumerate()
+            .map(|(index, switch)| switch.state().map_err(|error| Error { index, error }))
+    }
+}
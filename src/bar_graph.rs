@@ -0,0 +1,133 @@
+//! Drives an LED bar graph, or level meter, made of discrete [OutputSwitch](crate::OutputSwitch)es.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::IntoSwitch;
+//! use switch_hal::bar_graph::BarGraph;
+//! # let pins = [(); 5].map(|_| mock::Pin::new());
+//! // battery voltage in millivolts
+//! let mut battery = BarGraph::new(pins.map(|pin| pin.into_active_high_switch()), 3300, 4200);
+//! battery.set_blink_over_range(true);
+//! battery.show(3900).unwrap();
+//!
+//! // from a periodic timer
+//! battery.tick().unwrap();
+//! ```
+use crate::bank::Error;
+use crate::{OutputSwitch, SwitchState};
+
+/// How a [BarGraph](BarGraph) displays a level
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    /// Every segment up to and including the level is lit
+    Bar,
+    /// Only the segment at the level is lit
+    Dot,
+}
+
+/// Maps a value in a range to a number of lit segments.
+///
+/// `segments[0]` is the bottom of the graph.
+/// Only segments whose state changes are written.
+pub struct BarGraph<S, const N: usize> {
+    segments: [S; N],
+    shown: [Option<SwitchState>; N],
+    min: i32,
+    max: i32,
+    mode: Mode,
+    blink_over_range: bool,
+    blink_on: bool,
+    level: usize,
+    over_range: bool,
+}
+
+impl<S: OutputSwitch, const N: usize> BarGraph<S, N> {
+    /// Constructs a new [BarGraph](BarGraph) in [Bar](Mode::Bar) mode, displaying values from `min` to `max`
+    ///
+    /// The segments are not written until the first call to [show](BarGraph::show).
+    ///
+    /// # Panics
+    /// Panics if `min` is not less than `max`.
+    pub fn new(segments: [S; N], min: i32, max: i32) -> Self {
+        assert!(min < max, "BarGraph range must not be empty");
+        BarGraph {
+            segments,
+            shown: [None; N],
+            min,
+            max,
+            mode: Mode::Bar,
+            blink_over_range: false,
+            blink_on: true,
+            level: 0,
+            over_range: false,
+        }
+    }
+
+    /// Consumes the [BarGraph](BarGraph) and returns the underlying segments
+    pub fn into_inner(self) -> [S; N] {
+        self.segments
+    }
+
+    /// Selects [Bar](Mode::Bar) or [Dot](Mode::Dot) mode, taking effect on the next write
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    /// When enabled, the top segment blinks on every [tick](BarGraph::tick) while the value is above the range
+    pub fn set_blink_over_range(&mut self, blink: bool) {
+        self.blink_over_range = blink;
+    }
+
+    /// Displays `value`, clamped to the graph's range
+    pub fn show(&mut self, value: i32) -> Result<(), Error<S::Error>> {
+        self.level = self.level_of(value);
+        self.over_range = value > self.max;
+        if !self.over_range {
+            self.blink_on = true;
+        }
+        self.refresh()
+    }
+
+    /// Advances the over range blink.
+    ///
+    /// Call this periodically at twice the desired blink rate.
+    /// Nothing is written unless the top segment is blinking.
+    pub fn tick(&mut self) -> Result<(), Error<S::Error>> {
+        if self.over_range && self.blink_over_range {
+            self.blink_on = !self.blink_on;
+            self.refresh()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of segments lit for `value`
+    fn level_of(&self, value: i32) -> usize {
+        let value = value.clamp(self.min, self.max);
+        let span = i64::from(self.max) - i64::from(self.min);
+        let offset = i64::from(value) - i64::from(self.min);
+        (offset * N as i64 / span) as usize
+    }
+
+    fn refresh(&mut self) -> Result<(), Error<S::Error>> {
+        for index in 0..N {
+            let lit = match self.mode {
+                Mode::Bar => index < self.level,
+                Mode::Dot => index + 1 == self.level,
+            };
+            let blinking_off = self.over_range && self.blink_over_range && !self.blink_on;
+            let state = SwitchState::from(lit && !(blinking_off && index + 1 == N));
+
+            if self.shown[index] != Some(state) {
+                self.shown[index] = None;
+                self.segments[index]
+                    .set_state(state)
+                    .map_err(|error| Error { index, error })?;
+                self.shown[index] = Some(state);
+            }
+        }
+        Ok(())
+    }
+}
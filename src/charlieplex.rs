@@ -0,0 +1,193 @@
+//! Drives charlieplexed LEDs from pins that can switch between output and input (high impedance)
+//! through the [IoPin](embedded_hal::digital::v2::IoPin) trait.
+//!
+//! `N` pins drive up to `N * (N - 1)` LEDs, one for every ordered pair of pins.
+//! LED indices are assigned in anode major order, skipping the pin itself:
+//! with 3 pins, LEDs `0` and `1` have their anode on pin 0 and cathodes on pins 1 and 2,
+//! LEDs `2` and `3` have their anode on pin 1 and cathodes on pins 0 and 2, and so on.
+//!
+//! The driver scans one anode pin per [tick](Charlieplex::tick),
+//! driving the anode high and the cathodes of every lit LED low,
+//! while every other pin is left floating as an input.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::charlieplex::Charlieplex;
+//! # let pins = [(); 3].map(|_| mock::Pin::new());
+//! let mut leds = Charlieplex::new(pins);
+//! leds.set(4, true);
+//!
+//! // from a periodic timer
+//! leds.tick().unwrap();
+//! ```
+use core::mem;
+
+use embedded_hal::digital::v2::{InputPin, IoPin, OutputPin, PinState};
+
+use crate::bank;
+
+/// Error returned by a [Charlieplex](Charlieplex) driver
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error<E> {
+    /// A pin could not change mode
+    Pin(bank::Error<E>),
+    /// A pin was lost because a previous mode change failed
+    Lost(usize),
+}
+
+/// Returns the `(anode, cathode)` pins of LED `index` for `pins` pins,
+/// or `None` if the index is out of range.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::charlieplex::pins_for;
+///
+/// assert_eq!(Some((0, 1)), pins_for(0, 3));
+/// assert_eq!(Some((1, 0)), pins_for(2, 3));
+/// assert_eq!(Some((2, 1)), pins_for(5, 3));
+/// assert_eq!(None, pins_for(6, 3));
+/// ```
+pub fn pins_for(index: usize, pins: usize) -> Option<(usize, usize)> {
+    if pins < 2 || index >= pins * (pins - 1) {
+        return None;
+    }
+    let anode = index / (pins - 1);
+    let offset = index % (pins - 1);
+    let cathode = if offset < anode { offset } else { offset + 1 };
+    Some((anode, cathode))
+}
+
+enum Line<I, O> {
+    Input(I),
+    Output(O),
+    Lost,
+}
+
+/// A set of charlieplexed LEDs driven from `N` reconfigurable pins.
+///
+/// From 2 up to 32 pins are supported.
+pub struct Charlieplex<I, O, const N: usize> {
+    lines: [Line<I, O>; N],
+    lit: [u32; N],
+    anode: usize,
+}
+
+impl<I, O, E, const N: usize> Charlieplex<I, O, N>
+where
+    I: InputPin + IoPin<I, O, Error = E>,
+    O: OutputPin + IoPin<I, O, Error = E>,
+{
+    /// Constructs a new [Charlieplex](Charlieplex) driver from pins in input mode, with every LED off
+    ///
+    /// # Panics
+    /// Panics if there are fewer than 2 or more than 32 pins.
+    pub fn new(pins: [I; N]) -> Self {
+        assert!(
+            (2..=32).contains(&N),
+            "Charlieplex supports from 2 to 32 pins"
+        );
+        Charlieplex {
+            lines: pins.map(Line::Input),
+            lit: [0; N],
+            anode: N - 1,
+        }
+    }
+
+    /// Number of LEDs that can be driven
+    pub fn len(&self) -> usize {
+        N * N.saturating_sub(1)
+    }
+
+    /// Returns true if there are too few pins to drive any LEDs
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Turns LED `index` on or off, from the next time its anode is scanned
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    pub fn set(&mut self, index: usize, on: bool) {
+        let (anode, cathode) = pins_for(index, N).expect("LED index out of range");
+        if on {
+            self.lit[anode] |= 1 << cathode;
+        } else {
+            self.lit[anode] &= !(1 << cathode);
+        }
+    }
+
+    /// Returns whether LED `index` is set to be lit
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    pub fn is_set(&self, index: usize) -> bool {
+        let (anode, cathode) = pins_for(index, N).expect("LED index out of range");
+        self.lit[anode] & (1 << cathode) != 0
+    }
+
+    /// Turns every LED off, from the next [tick](Charlieplex::tick)
+    pub fn clear(&mut self) {
+        self.lit = [0; N];
+    }
+
+    /// Scans the next anode pin.
+    ///
+    /// The previous anode and every pin that is not part of the new scan are floated first,
+    /// then the cathodes are driven low, and finally the anode is driven high,
+    /// so no unintended LED is ever lit.
+    pub fn tick(&mut self) -> Result<(), Error<E>> {
+        let previous = self.anode;
+        self.anode = (self.anode + 1) % N;
+        let cathodes = self.lit[self.anode];
+        // the previous anode may be one of the new cathodes,
+        // and must not stay high while the other cathodes are pulled low
+        self.float(previous)?;
+        for index in 0..N {
+            if index != self.anode && cathodes & (1 << index) == 0 {
+                self.float(index)?;
+            }
+        }
+        if cathodes == 0 {
+            return self.float(self.anode);
+        }
+        for index in 0..N {
+            if cathodes & (1 << index) != 0 {
+                self.drive(index, PinState::Low)?;
+            }
+        }
+        self.drive(self.anode, PinState::High)
+    }
+
+    /// Floats every pin, turning every LED off until the next [tick](Charlieplex::tick)
+    pub fn blank(&mut self) -> Result<(), Error<E>> {
+        for index in 0..N {
+            self.float(index)?;
+        }
+        Ok(())
+    }
+
+    fn float(&mut self, index: usize) -> Result<(), Error<E>> {
+        let line = match mem::replace(&mut self.lines[index], Line::Lost) {
+            Line::Input(pin) => Ok(pin),
+            Line::Output(pin) => pin.into_input_pin(),
+            Line::Lost => return Err(Error::Lost(index)),
+        };
+        self.lines[index] =
+            Line::Input(line.map_err(|error| Error::Pin(bank::Error { index, error }))?);
+        Ok(())
+    }
+
+    fn drive(&mut self, index: usize, state: PinState) -> Result<(), Error<E>> {
+        let line = match mem::replace(&mut self.lines[index], Line::Lost) {
+            Line::Input(pin) => pin.into_output_pin(state),
+            Line::Output(pin) => pin.into_output_pin(state),
+            Line::Lost => return Err(Error::Lost(index)),
+        };
+        self.lines[index] =
+            Line::Output(line.map_err(|error| Error::Pin(bank::Error { index, error }))?);
+        Ok(())
+    }
+}
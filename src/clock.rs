@@ -0,0 +1,106 @@
+//! Time source for timed switch behaviours, such as debouncing, pulses and blinking.
+//!
+//! Every HAL exposes its timers differently, so timed code takes a [Clock](Clock),
+//! which only reports the current [Instant](Instant).
+//! Any closure returning an [Instant](Instant) is a [Clock](Clock),
+//! so wrapping a HAL timer is usually a one liner.
+//!
+//! With the `fugit` feature enabled, `fugit::Instant` implements [Instant](Instant),
+//! which covers the monotonic timers of most HALs and RTIC.
+//! HALs built on `embedded-time` are covered by the `embedded-time` feature,
+//! which implements [Instant](Instant) for `embedded_time::Instant`.
+//! For tests, [MockClock](crate::mock::MockClock) is advanced manually.
+//!
+//! # Examples
+//!
+//! ```
+//! use switch_hal::clock::{Clock, Millis};
+//!
+//! fn wait_for<C: Clock>(clock: &C, millis: u64) {
+//!     let start = clock.now();
+//!     while clock.millis_since(start) < millis {}
+//! }
+//!
+//! # struct Timer(core::cell::Cell<u64>);
+//! # impl Timer { fn millis(&self) -> u64 { self.0.set(self.0.get() + 1); self.0.get() } }
+//! # let timer = Timer(core::cell::Cell::new(0));
+//! let clock = || Millis(timer.millis());
+//! wait_for(&clock, 10);
+//! ```
+
+/// A point in time, as reported by a [Clock](Clock)
+pub trait Instant: Copy {
+    /// Milliseconds elapsed since `earlier`, or `0` if `earlier` is after `self`
+    fn millis_since(self, earlier: Self) -> u64;
+}
+
+/// A monotonic time source
+pub trait Clock {
+    type Instant: Instant;
+
+    /// The current time
+    fn now(&self) -> Self::Instant;
+
+    /// Milliseconds elapsed since `earlier`
+    fn millis_since(&self, earlier: Self::Instant) -> u64 {
+        self.now().millis_since(earlier)
+    }
+}
+
+impl<F, I> Clock for F
+where
+    F: Fn() -> I,
+    I: Instant,
+{
+    type Instant = I;
+
+    fn now(&self) -> Self::Instant {
+        self()
+    }
+}
+
+/// [Instant](Instant) counting milliseconds from an arbitrary epoch, such as boot
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct Millis(pub u64);
+
+impl Instant for Millis {
+    fn millis_since(self, earlier: Self) -> u64 {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+/// Handles timer wrap around, as long as `earlier` is less than half the timer range ago
+#[cfg(feature = "fugit")]
+impl<const NOM: u32, const DENOM: u32> Instant for fugit::Instant<u32, NOM, DENOM> {
+    fn millis_since(self, earlier: Self) -> u64 {
+        self.checked_duration_since(earlier)
+            .map_or(0, |elapsed| u64::from(elapsed.to_millis()))
+    }
+}
+
+#[cfg(feature = "fugit")]
+impl<const NOM: u32, const DENOM: u32> Instant for fugit::Instant<u64, NOM, DENOM> {
+    fn millis_since(self, earlier: Self) -> u64 {
+        self.checked_duration_since(earlier)
+            .map_or(0, |elapsed| elapsed.to_millis())
+    }
+}
+
+/// Handles timer wrap around, as long as `earlier` is less than half the timer range ago.
+///
+/// Saturates at `u64::MAX` milliseconds if the elapsed time cannot be converted.
+#[cfg(feature = "embedded-time")]
+impl<C> Instant for embedded_time::Instant<C>
+where
+    C: embedded_time::Clock,
+    u64: core::convert::TryFrom<C::T>,
+{
+    fn millis_since(self, earlier: Self) -> u64 {
+        use core::convert::TryFrom;
+        use embedded_time::duration::Milliseconds;
+
+        self.checked_duration_since(&earlier).map_or(0, |elapsed| {
+            Milliseconds::<u64>::try_from(elapsed).map_or(u64::MAX, |millis| millis.0)
+        })
+    }
+}
@@ -0,0 +1,353 @@
+//! GPIO expander drivers.
+//!
+//! Each driver implements the [port](crate::port) driver traits,
+//! so individual pins are handed out through a [Port](crate::port::Port),
+//! which caches the output register to avoid read-modify-write races,
+//! and wrapped in a [Switch](crate::Switch) like any other pin.
+//! Application code only sees switches and does not care which bus the expander is on.
+//!
+//! The MCP23x17 is available on either bus, as the I2C [Mcp23017](Mcp23017)
+//! and the SPI [Mcp23s17](Mcp23s17), which share everything but the constructor.
+//! Both can raise an interrupt when an input changes, which is configured per switch
+//! through [InterruptSwitch](crate::interrupt::InterruptSwitch).
+//!
+//! Drivers own their bus. To put several devices on one bus,
+//! pass each driver a proxy from a bus sharing crate such as `shared-bus`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use embedded_hal_mock::eh0::i2c::{Mock, Transaction};
+//! # let mut i2c = Mock::new(&[
+//! #     Transaction::write(0x20, vec![0x00, 0x01, 0x00]),
+//! #     Transaction::write(0x20, vec![0x0C, 0x01, 0x00]),
+//! #     Transaction::write_read(0x20, vec![0x12], vec![0x00, 0x00]),
+//! #     Transaction::write(0x20, vec![0x14, 0x00, 0x01]),
+//! # ]);
+//! # let mut done = i2c.clone();
+//! use switch_hal::{InputSwitch, IntoSwitch, OutputSwitch};
+//! use switch_hal::expander::Mcp23017;
+//! use switch_hal::port::Port;
+//!
+//! let mut mcp = Mcp23017::new(i2c, 0x20);
+//! // GPA0 is a button, everything else is an output
+//! mcp.set_directions(0x0001).unwrap();
+//! mcp.set_pull_ups(0x0001).unwrap();
+//!
+//! let port = Port::new(mcp);
+//! let button = port.input(0).into_active_low_switch();
+//! let mut led = port.output(8).into_active_high_switch();
+//!
+//! port.refresh().unwrap();
+//! if button.is_active().unwrap() {
+//!     led.on().unwrap();
+//! }
+//! # done.done();
+//! ```
+use embedded_hal::blocking::{i2c, spi};
+use embedded_hal::digital::v2::OutputPin;
+
+use crate::port::{
+    InputPortDriver, InterruptConfig, InterruptPortDriver, InterruptStatus, OutputPortDriver,
+};
+
+/// Longest run of registers transferred at once
+const MAX_REGISTERS: usize = 4;
+
+/// PCF8574 8 bit quasi-bidirectional I2C expander.
+///
+/// Pins used as inputs must be driven high, which is their power on state,
+/// so construct the port with `Port::with_outputs(pcf, 0xFF)` and never turn an input's handle low.
+pub struct Pcf8574<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C, E> Pcf8574<I2C>
+where
+    I2C: i2c::Write<Error = E> + i2c::Read<Error = E>,
+{
+    /// Constructs a new [Pcf8574](Pcf8574) driver at the 7 bit `address`
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Pcf8574 { i2c, address }
+    }
+
+    /// Consumes the [Pcf8574](Pcf8574) driver and returns the bus
+    pub fn into_inner(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E> OutputPortDriver for Pcf8574<I2C>
+where
+    I2C: i2c::Write<Error = E> + i2c::Read<Error = E>,
+{
+    type Error = E;
+
+    fn write(&mut self, outputs: u32) -> Result<(), Self::Error> {
+        self.i2c.write(self.address, &[outputs as u8])
+    }
+}
+
+impl<I2C, E> InputPortDriver for Pcf8574<I2C>
+where
+    I2C: i2c::Write<Error = E> + i2c::Read<Error = E>,
+{
+    type Error = E;
+
+    fn read(&mut self) -> Result<u32, Self::Error> {
+        let mut buffer = [0];
+        self.i2c.read(self.address, &mut buffer)?;
+        Ok(u32::from(buffer[0]))
+    }
+}
+
+/// Register level access to a MCP23x17, independent of the bus it is on
+pub trait RegisterBus {
+    type Error;
+
+    /// Writes `values` to consecutive registers, starting at `register`
+    fn write_registers(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error>;
+
+    /// Reads consecutive registers, starting at `register`, into `values`
+    fn read_registers(&mut self, register: u8, values: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// [RegisterBus](RegisterBus) for the I2C MCP23017
+pub struct I2cBus<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C, E> RegisterBus for I2cBus<I2C>
+where
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
+{
+    type Error = E;
+
+    fn write_registers(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        let mut buffer = [0; 1 + MAX_REGISTERS];
+        buffer[0] = register;
+        buffer[1..=values.len()].copy_from_slice(values);
+        self.i2c.write(self.address, &buffer[..=values.len()])
+    }
+
+    fn read_registers(&mut self, register: u8, values: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c.write_read(self.address, &[register], values)
+    }
+}
+
+/// Errors from a [SpiBus](SpiBus)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpiError<S, P> {
+    /// The transfer failed
+    Spi(S),
+    /// The chip select pin could not be driven
+    ChipSelect(P),
+}
+
+/// [RegisterBus](RegisterBus) for the SPI MCP23S17
+///
+/// The chip select pin is driven low for the duration of every transfer.
+pub struct SpiBus<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+    opcode: u8,
+}
+
+impl<SPI, CS, E> SpiBus<SPI, CS>
+where
+    SPI: spi::Write<u8, Error = E> + spi::Transfer<u8, Error = E>,
+    CS: OutputPin,
+{
+    fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(&mut SPI) -> Result<R, E>,
+    ) -> Result<R, SpiError<E, CS::Error>> {
+        self.cs.set_low().map_err(SpiError::ChipSelect)?;
+        let result = f(&mut self.spi).map_err(SpiError::Spi);
+        self.cs.set_high().map_err(SpiError::ChipSelect)?;
+        result
+    }
+}
+
+impl<SPI, CS, E> RegisterBus for SpiBus<SPI, CS>
+where
+    SPI: spi::Write<u8, Error = E> + spi::Transfer<u8, Error = E>,
+    CS: OutputPin,
+{
+    type Error = SpiError<E, CS::Error>;
+
+    fn write_registers(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        let mut buffer = [0; 2 + MAX_REGISTERS];
+        buffer[0] = self.opcode;
+        buffer[1] = register;
+        buffer[2..2 + values.len()].copy_from_slice(values);
+        self.transaction(|spi| spi.write(&buffer[..2 + values.len()]))
+    }
+
+    fn read_registers(&mut self, register: u8, values: &mut [u8]) -> Result<(), Self::Error> {
+        let mut buffer = [0; 2 + MAX_REGISTERS];
+        buffer[0] = self.opcode | 1;
+        buffer[1] = register;
+        let len = 2 + values.len();
+        self.transaction(|spi| spi.transfer(&mut buffer[..len]).map(|_| ()))?;
+        values.copy_from_slice(&buffer[2..len]);
+        Ok(())
+    }
+}
+
+/// MCP23x17 register addresses, with `IOCON.BANK = 0` (the power on default)
+pub mod register {
+    pub const IODIRA: u8 = 0x00;
+    pub const GPINTENA: u8 = 0x04;
+    pub const DEFVALA: u8 = 0x06;
+    pub const INTCONA: u8 = 0x08;
+    pub const IOCON: u8 = 0x0A;
+    pub const GPPUA: u8 = 0x0C;
+    pub const INTFA: u8 = 0x0E;
+    pub const INTCAPA: u8 = 0x10;
+    pub const GPIOA: u8 = 0x12;
+    pub const OLATA: u8 = 0x14;
+}
+
+/// MCP23x17 `IOCON` bits, for [Mcp23x17::set_config](Mcp23x17::set_config)
+pub mod iocon {
+    /// `INTA` and `INTB` are both raised by a change on either port
+    pub const MIRROR: u8 = 0x40;
+    /// The MCP23S17 only responds to its own hardware address
+    pub const HAEN: u8 = 0x08;
+    /// The interrupt pins are open drain
+    pub const ODR: u8 = 0x04;
+    /// The interrupt pins are active high
+    pub const INTPOL: u8 = 0x02;
+}
+
+/// MCP23x17 16 bit expander.
+///
+/// Pin `0` is `GPA0` and pin `8` is `GPB0`.
+/// Every pin is an input at power on, so outputs must be configured with
+/// [set_directions](Mcp23x17::set_directions) before handing the driver to a [Port](crate::port::Port).
+pub struct Mcp23x17<B> {
+    bus: B,
+}
+
+/// MCP23017 I2C expander
+pub type Mcp23017<I2C> = Mcp23x17<I2cBus<I2C>>;
+
+impl<I2C, E> Mcp23x17<I2cBus<I2C>>
+where
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
+{
+    /// Constructs a new [Mcp23017](Mcp23017) driver at the 7 bit `address`
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Mcp23x17 {
+            bus: I2cBus { i2c, address },
+        }
+    }
+
+    /// Consumes the [Mcp23017](Mcp23017) driver and returns the bus
+    pub fn into_inner(self) -> I2C {
+        self.bus.i2c
+    }
+}
+
+/// MCP23S17 SPI expander
+pub type Mcp23s17<SPI, CS> = Mcp23x17<SpiBus<SPI, CS>>;
+
+impl<SPI, CS, E> Mcp23x17<SpiBus<SPI, CS>>
+where
+    SPI: spi::Write<u8, Error = E> + spi::Transfer<u8, Error = E>,
+    CS: OutputPin,
+{
+    /// Constructs a new [Mcp23s17](Mcp23s17) driver with the 3 bit hardware `address`.
+    ///
+    /// The hardware address is ignored by the device until [iocon::HAEN](iocon::HAEN)
+    /// is set with [set_config](Mcp23x17::set_config), so use `0` until then.
+    ///
+    /// # Panics
+    /// Panics if `address` does not fit in 3 bits.
+    pub fn new(spi: SPI, cs: CS, address: u8) -> Self {
+        assert!(address < 8, "MCP23S17 hardware address is 3 bits");
+        Mcp23x17 {
+            bus: SpiBus {
+                spi,
+                cs,
+                opcode: 0x40 | (address << 1),
+            },
+        }
+    }
+
+    /// Consumes the [Mcp23s17](Mcp23s17) driver and returns the bus and chip select pin
+    pub fn into_inner(self) -> (SPI, CS) {
+        (self.bus.spi, self.bus.cs)
+    }
+}
+
+impl<B: RegisterBus> Mcp23x17<B> {
+    /// Writes the `IOCON` register, built from the [iocon](iocon) bits
+    pub fn set_config(&mut self, config: u8) -> Result<(), B::Error> {
+        self.bus.write_registers(register::IOCON, &[config])
+    }
+
+    /// Sets which pins are inputs, where a set bit is an input
+    pub fn set_directions(&mut self, inputs: u16) -> Result<(), B::Error> {
+        self.write_pair(register::IODIRA, inputs)
+    }
+
+    /// Enables the internal pull up resistor of every pin whose bit is set
+    pub fn set_pull_ups(&mut self, pull_ups: u16) -> Result<(), B::Error> {
+        self.write_pair(register::GPPUA, pull_ups)
+    }
+
+    fn write_pair(&mut self, register: u8, value: u16) -> Result<(), B::Error> {
+        self.bus.write_registers(register, &value.to_le_bytes())
+    }
+
+    fn read_pair(&mut self, register: u8) -> Result<u16, B::Error> {
+        let mut buffer = [0; 2];
+        self.bus.read_registers(register, &mut buffer)?;
+        Ok(u16::from_le_bytes(buffer))
+    }
+}
+
+impl<B: RegisterBus> OutputPortDriver for Mcp23x17<B> {
+    type Error = B::Error;
+
+    /// Writes both output latches
+    fn write(&mut self, outputs: u32) -> Result<(), Self::Error> {
+        self.write_pair(register::OLATA, outputs as u16)
+    }
+}
+
+impl<B: RegisterBus> InputPortDriver for Mcp23x17<B> {
+    type Error = B::Error;
+
+    /// Reads both ports
+    fn read(&mut self) -> Result<u32, Self::Error> {
+        self.read_pair(register::GPIOA).map(u32::from)
+    }
+}
+
+/// The MCP23x17 compares each pin against its previous state to interrupt on both edges,
+/// or against a default value to interrupt on a single edge.
+/// In the latter case the interrupt is raised for as long as the pin differs from the default,
+/// so it fires again after being serviced if the input is still active.
+impl<B: RegisterBus> InterruptPortDriver for Mcp23x17<B> {
+    fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Self::Error> {
+        let compare = config.rising ^ config.falling;
+        self.write_pair(register::DEFVALA, config.falling as u16)?;
+        self.write_pair(register::INTCONA, compare as u16)?;
+        self.write_pair(register::GPINTENA, config.enabled as u16)
+    }
+
+    /// Reads `INTF` and `INTCAP` at once, which clears the interrupt
+    fn read_interrupts(&mut self) -> Result<InterruptStatus, Self::Error> {
+        let mut buffer = [0; 4];
+        self.bus.read_registers(register::INTFA, &mut buffer)?;
+        Ok(InterruptStatus {
+            pending: u32::from(u16::from_le_bytes([buffer[0], buffer[1]])),
+            captured: u32::from(u16::from_le_bytes([buffer[2], buffer[3]])),
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{ActiveHigh, ActiveLow, InputSwitch, Switch};
+use crate::{ActiveHigh, ActiveLow, InputSwitch, Switch, SwitchState};
 use embedded_hal::digital::v2::InputPin;
 
 impl<T: InputPin> InputSwitch for Switch<T, ActiveHigh> {
@@ -16,3 +16,27 @@ impl<T: InputPin> InputSwitch for Switch<T, ActiveLow> {
         self.pin.is_low()
     }
 }
+
+impl<S: InputSwitch + ?Sized> InputSwitch for &S {
+    type Error = S::Error;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        (**self).is_active()
+    }
+
+    fn state(&self) -> Result<SwitchState, Self::Error> {
+        (**self).state()
+    }
+}
+
+impl<S: InputSwitch + ?Sized> InputSwitch for &mut S {
+    type Error = S::Error;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        (**self).is_active()
+    }
+
+    fn state(&self) -> Result<SwitchState, Self::Error> {
+        (**self).state()
+    }
+}
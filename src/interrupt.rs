@@ -0,0 +1,155 @@
+//! Interrupt support for [InputSwitch](crate::InputSwitch)es backed by EXTI-style pins.
+//!
+//! `embedded-hal` does not (yet) define a trait for external interrupts,
+//! so HALs (or thin wrappers around them) can implement [InterruptPin](InterruptPin)
+//! to let a [Switch](crate::Switch) configure and service the interrupt in terms of
+//! activation and deactivation rather than rising and falling edges.
+use crate::{ActiveHigh, ActiveLow, InputSwitch, Switch};
+use embedded_hal::digital::v2::InputPin;
+
+/// Electrical edge on which a pin raises an interrupt
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Edge {
+    Rising,
+    Falling,
+    RisingFalling,
+}
+
+/// Logical transition on which a switch raises an interrupt
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Trigger {
+    /// The switch became active, i.e. a button was pressed
+    Activation,
+    /// The switch became inactive, i.e. a button was released
+    Deactivation,
+    /// Either transition
+    Both,
+}
+
+/// Represents a pin that can raise an external interrupt.
+///
+/// Modeled after the `ExtiPin` traits found in several HAL crates.
+/// Implement this for your HAL's pin type (or a wrapper around it) to get
+/// an [InterruptSwitch](InterruptSwitch) implementation for [Switch](crate::Switch).
+pub trait InterruptPin {
+    /// Selects the edge(s) the interrupt fires on
+    fn trigger_on_edge(&mut self, edge: Edge);
+
+    /// Unmasks the interrupt line for this pin
+    fn enable_interrupt(&mut self);
+
+    /// Masks the interrupt line for this pin
+    fn disable_interrupt(&mut self);
+
+    /// Returns true if the interrupt is pending for this pin
+    fn check_interrupt(&self) -> bool;
+
+    /// Clears the pending interrupt for this pin
+    fn clear_interrupt_pending_bit(&mut self);
+}
+
+/// Represents an [InputSwitch](crate::InputSwitch) that can wake the processor or raise an interrupt
+/// when it is activated or deactivated.
+///
+/// # Notes
+/// This is only available if the underlying hal has implemented [InterruptPin](InterruptPin)
+pub trait InterruptSwitch: InputSwitch {
+    /// Selects the transition(s) the interrupt fires on.
+    ///
+    /// The edge configured on the underlying pin is derived from the switch's `ActiveLevel`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::IntoSwitch;
+    /// use switch_hal::interrupt::{Edge, InterruptSwitch, Trigger};
+    /// # let pin = mock::Pin::with_state(mock::State::High);
+    /// let mut button = pin.into_active_low_switch();
+    /// button.trigger_on(Trigger::Activation);
+    /// button.enable_interrupt();
+    /// # assert_eq!(Some(Edge::Falling), button.into_pin().edge());
+    /// ```
+    fn trigger_on(&mut self, trigger: Trigger);
+
+    /// Enables the interrupt for this switch
+    fn enable_interrupt(&mut self);
+
+    /// Disables the interrupt for this switch
+    fn disable_interrupt(&mut self);
+
+    /// Returns true if this switch raised the pending interrupt
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::IntoSwitch;
+    /// use switch_hal::interrupt::{InterruptSwitch, Trigger};
+    /// # let pin = mock::Pin::with_state(mock::State::High);
+    /// # let mut button = pin.into_active_low_switch();
+    /// // in the interrupt handler
+    /// if button.check_interrupt() {
+    ///     // handle the button press
+    ///     button.clear_interrupt();
+    /// }
+    /// ```
+    fn check_interrupt(&self) -> bool;
+
+    /// Clears the pending interrupt for this switch
+    fn clear_interrupt(&mut self);
+}
+
+impl<T: InputPin + InterruptPin> InterruptSwitch for Switch<T, ActiveHigh> {
+    fn trigger_on(&mut self, trigger: Trigger) {
+        let edge = match trigger {
+            Trigger::Activation => Edge::Rising,
+            Trigger::Deactivation => Edge::Falling,
+            Trigger::Both => Edge::RisingFalling,
+        };
+        self.pin.trigger_on_edge(edge);
+    }
+
+    fn enable_interrupt(&mut self) {
+        self.pin.enable_interrupt()
+    }
+
+    fn disable_interrupt(&mut self) {
+        self.pin.disable_interrupt()
+    }
+
+    fn check_interrupt(&self) -> bool {
+        self.pin.check_interrupt()
+    }
+
+    fn clear_interrupt(&mut self) {
+        self.pin.clear_interrupt_pending_bit()
+    }
+}
+
+impl<T: InputPin + InterruptPin> InterruptSwitch for Switch<T, ActiveLow> {
+    fn trigger_on(&mut self, trigger: Trigger) {
+        let edge = match trigger {
+            Trigger::Activation => Edge::Falling,
+            Trigger::Deactivation => Edge::Rising,
+            Trigger::Both => Edge::RisingFalling,
+        };
+        self.pin.trigger_on_edge(edge);
+    }
+
+    fn enable_interrupt(&mut self) {
+        self.pin.enable_interrupt()
+    }
+
+    fn disable_interrupt(&mut self) {
+        self.pin.disable_interrupt()
+    }
+
+    fn check_interrupt(&self) -> bool {
+        self.pin.check_interrupt()
+    }
+
+    fn clear_interrupt(&mut self) {
+        self.pin.clear_interrupt_pending_bit()
+    }
+}
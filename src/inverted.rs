@@ -0,0 +1,160 @@
+use crate::{
+    ActiveHigh, ActiveLow, InputSwitch, OutputSwitch, StatefulOutputSwitch, Switch,
+    ToggleableOutputSwitch,
+};
+
+/// Adapter that logically inverts any switch.
+///
+/// An inverted [InputSwitch](trait.InputSwitch.html) is active when the wrapped switch is inactive
+/// and an inverted [OutputSwitch](trait.OutputSwitch.html) turns the wrapped switch off when turned on.
+///
+/// This is useful when the polarity is decided downstream of the switch,
+/// e.g. by an inverting transistor stage.
+///
+/// **Prefer the [Invert](trait.Invert.html) trait over calling [new](#method.new) directly.**
+pub struct Inverted<S> {
+    switch: S,
+}
+
+impl<S> Inverted<S> {
+    /// Wraps `switch`, inverting its semantics
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{Inverted, IntoSwitch, OutputSwitch};
+    /// # let pin = mock::Pin::new();
+    /// let mut led = Inverted::new(pin.into_active_high_switch());
+    /// led.on().ok();
+    /// ```
+    pub fn new(switch: S) -> Self {
+        Inverted { switch }
+    }
+
+    /// Consumes the [Inverted](struct.Inverted.html) adapter and returns the wrapped switch
+    pub fn into_inner(self) -> S {
+        self.switch
+    }
+
+    /// Removes the inversion, returning the wrapped switch.
+    ///
+    /// This shadows [Invert::inverted](trait.Invert.html#method.inverted),
+    /// so inverting twice is a no-op at the type level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use switch_hal::Invert;
+    /// # struct Transistor;
+    /// # let transistor = Transistor;
+    /// let transistor: Transistor = transistor.inverted().inverted();
+    /// ```
+    pub fn inverted(self) -> S {
+        self.switch
+    }
+}
+
+impl<T> Switch<T, ActiveHigh> {
+    /// Inverts the switch by flipping its `ActiveLevel`.
+    ///
+    /// This shadows [Invert::inverted](trait.Invert.html#method.inverted),
+    /// so no [Inverted](struct.Inverted.html) adapter is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{ActiveLow, IntoSwitch, Switch};
+    /// # let pin = mock::Pin::new();
+    /// let led: Switch<_, ActiveLow> = pin.into_active_high_switch().inverted();
+    /// ```
+    pub fn inverted(self) -> Switch<T, ActiveLow> {
+        Switch::new(self.pin)
+    }
+}
+
+impl<T> Switch<T, ActiveLow> {
+    /// Inverts the switch by flipping its `ActiveLevel`.
+    ///
+    /// This shadows [Invert::inverted](trait.Invert.html#method.inverted),
+    /// so no [Inverted](struct.Inverted.html) adapter is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{ActiveHigh, IntoSwitch, Switch};
+    /// # let pin = mock::Pin::new();
+    /// let led: Switch<_, ActiveHigh> = pin.into_active_low_switch().inverted();
+    /// ```
+    pub fn inverted(self) -> Switch<T, ActiveHigh> {
+        Switch::new(self.pin)
+    }
+}
+
+/// Convenience function for wrapping any switch in an [Inverted](struct.Inverted.html) adapter.
+pub trait Invert {
+    /// Consumes the switch, returning an [Inverted](struct.Inverted.html) switch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{Invert, InputSwitch, IntoSwitch};
+    /// # let pin = mock::Pin::with_state(mock::State::High);
+    /// # let button = pin.into_active_high_switch();
+    /// // the button is wired through an inverting buffer
+    /// let button = Invert::inverted(button);
+    /// assert!(!button.is_active().unwrap());
+    /// ```
+    fn inverted(self) -> Inverted<Self>
+    where
+        Self: core::marker::Sized;
+}
+
+impl<T> Invert for T {
+    fn inverted(self) -> Inverted<Self> {
+        Inverted::new(self)
+    }
+}
+
+impl<S: InputSwitch> InputSwitch for Inverted<S> {
+    type Error = <S as InputSwitch>::Error;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        self.switch.is_active().map(|active| !active)
+    }
+}
+
+impl<S: OutputSwitch> OutputSwitch for Inverted<S> {
+    type Error = <S as OutputSwitch>::Error;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        self.switch.off()
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.switch.on()
+    }
+}
+
+impl<S: StatefulOutputSwitch> StatefulOutputSwitch for Inverted<S> {
+    type Error = <S as StatefulOutputSwitch>::Error;
+
+    fn is_on(&mut self) -> Result<bool, Self::Error> {
+        self.switch.is_off()
+    }
+
+    fn is_off(&mut self) -> Result<bool, Self::Error> {
+        self.switch.is_on()
+    }
+}
+
+impl<S: ToggleableOutputSwitch> ToggleableOutputSwitch for Inverted<S> {
+    type Error = <S as ToggleableOutputSwitch>::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.switch.toggle()
+    }
+}
@@ -1,9 +1,36 @@
 #![no_std]
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
+
+#[cfg(feature = "std")]
+extern crate std;
 
 mod input;
+mod inverted;
 mod output;
+mod pin;
+
+pub use inverted::{Invert, Inverted};
+pub use pin::SwitchPin;
 
+pub mod bank;
+pub mod bar_graph;
+pub mod charlieplex;
+pub mod clock;
+pub mod expander;
+pub mod interrupt;
+pub mod logic;
+pub mod matrix;
 pub mod mock;
+pub mod port;
+pub mod rgb;
+pub mod seven_segment;
+pub mod shift_register;
+#[cfg(feature = "std")]
+pub mod sim;
+pub mod strap;
+pub mod tone;
+#[cfg(feature = "std")]
+pub mod trace;
 
 /// Represents an input switch, such as a button or a switch
 pub trait InputSwitch {
@@ -27,6 +54,21 @@ pub trait InputSwitch {
     /// }
     /// ```
     fn is_active(&self) -> Result<bool, Self::Error>;
+
+    /// Returns the current [SwitchState](enum.SwitchState.html) of the switch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{InputSwitch, IntoSwitch, SwitchState};
+    /// # let pin = mock::Pin::with_state(mock::State::Low);
+    /// let button = pin.into_active_low_switch();
+    /// assert_eq!(SwitchState::On, button.state().unwrap());
+    /// ```
+    fn state(&self) -> Result<SwitchState, Self::Error> {
+        self.is_active().map(SwitchState::from)
+    }
 }
 
 /// Represents an output switch, such as a LED "switch" or transistor
@@ -58,6 +100,25 @@ pub trait OutputSwitch {
     /// led.off().ok();
     /// ```
     fn off(&mut self) -> Result<(), Self::Error>;
+
+    /// Turns the switch on or off according to `state`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{OutputSwitch, IntoSwitch, SwitchState};
+    /// # let pin = mock::Pin::new();
+    /// # let alarm_raised = true;
+    /// let mut led = pin.into_active_high_switch();
+    /// led.set_state(SwitchState::from(alarm_raised)).ok();
+    /// ```
+    fn set_state(&mut self, state: SwitchState) -> Result<(), Self::Error> {
+        match state {
+            SwitchState::On => self.on(),
+            SwitchState::Off => self.off(),
+        }
+    }
 }
 
 /// Toggles the switch from it's current state to it's opposite state.
@@ -115,6 +176,88 @@ pub trait StatefulOutputSwitch {
     /// assert!(led.is_off().unwrap());
     /// ```
     fn is_off(&mut self) -> Result<bool, Self::Error>;
+
+    /// Returns the current [SwitchState](enum.SwitchState.html) of the switch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{OutputSwitch, IntoSwitch, StatefulOutputSwitch, SwitchState};
+    /// # let pin = mock::Pin::new();
+    /// let mut led = pin.into_active_high_switch();
+    /// led.on().ok();
+    /// assert_eq!(SwitchState::On, led.state().unwrap());
+    /// ```
+    fn state(&mut self) -> Result<SwitchState, Self::Error> {
+        self.is_on().map(SwitchState::from)
+    }
+}
+
+/// Sets the brightness of a switch, such as a LED driven by a PWM channel
+///
+/// # Notes
+/// This is only available if the underlying hal has implemented [PwmPin](embedded_hal::PwmPin)
+/// with a `u16` duty cycle. The PWM channel must already be enabled.
+pub trait DimmableOutputSwitch {
+    type Error;
+
+    /// Sets the brightness of the switch, from `0` (off) to `255` (fully on)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::{DimmableOutputSwitch, IntoSwitch};
+    /// # let pwm = mock::Pwm::new(1000);
+    /// let mut led = pwm.into_active_high_switch();
+    /// led.set_brightness(128).ok();
+    /// ```
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Self::Error>;
+}
+
+/// Logical state of a switch, independent of whether it is active high or active low
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::SwitchState;
+///
+/// assert_eq!(SwitchState::On, SwitchState::from(true));
+/// assert_eq!(SwitchState::Off, !SwitchState::On);
+/// assert!(bool::from(SwitchState::On));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SwitchState {
+    On,
+    Off,
+}
+
+impl core::ops::Not for SwitchState {
+    type Output = SwitchState;
+
+    fn not(self) -> Self::Output {
+        match self {
+            SwitchState::On => SwitchState::Off,
+            SwitchState::Off => SwitchState::On,
+        }
+    }
+}
+
+impl From<bool> for SwitchState {
+    fn from(on: bool) -> Self {
+        if on {
+            SwitchState::On
+        } else {
+            SwitchState::Off
+        }
+    }
+}
+
+impl From<SwitchState> for bool {
+    fn from(state: SwitchState) -> Self {
+        state == SwitchState::On
+    }
 }
 
 /// Zero sized struct for signaling to [Switch](struct.Switch.html) that it is active high
@@ -129,8 +272,8 @@ use core::marker::PhantomData;
 /// # Type Params
 /// - `IoPin` must be a type that implements either of the [InputPin](embedded_hal::digital::v2::InputPin) or [OutputPin](embedded_hal::digital::v2::OutputPin) traits.
 /// - `ActiveLevel` indicates whether the `Switch` is [ActiveHigh](ActiveHigh) or [ActiveLow](ActiveLow).
-///     `ActiveLevel` is not actually stored in the struct.
-///     It's [PhantomData](core::marker::PhantomData) used to indicate which implementation to use.
+///   `ActiveLevel` is not actually stored in the struct.
+///   It's [PhantomData](core::marker::PhantomData) used to indicate which implementation to use.
 pub struct Switch<IoPin, ActiveLevel> {
     pin: IoPin,
     active: PhantomData<ActiveLevel>,
@@ -183,7 +326,7 @@ impl<IoPin, ActiveLevel> Switch<IoPin, ActiveLevel> {
     /// ```
     pub fn new(pin: IoPin) -> Self {
         Switch {
-            pin: pin,
+            pin,
             active: PhantomData::<ActiveLevel>,
         }
     }
@@ -0,0 +1,133 @@
+//! Virtual [InputSwitch](crate::InputSwitch)es built from boolean combinations of other [InputSwitch](crate::InputSwitch)es.
+//!
+//! Combinators accept switches of different types, with different error types,
+//! and are themselves [InputSwitch](crate::InputSwitch)es, so they can be nested
+//! and treated exactly like a physical switch.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::{InputSwitch, IntoSwitch};
+//! use switch_hal::logic::{all, any};
+//! # let estop_a = mock::Pin::with_state(mock::State::Low).into_active_low_switch();
+//! # let estop_b = mock::Pin::with_state(mock::State::Low).into_active_low_switch();
+//! # let left_door = mock::Pin::with_state(mock::State::Low).into_active_high_switch();
+//! # let right_door = mock::Pin::with_state(mock::State::High).into_active_high_switch();
+//! let estop_closed = all(estop_a, estop_b);
+//! let door_open = any(left_door, right_door);
+//! assert!(estop_closed.is_active().unwrap());
+//! assert!(door_open.is_active().unwrap());
+//! ```
+use crate::{InputSwitch, Inverted};
+
+/// Error returned by a combinator, identifying which of the wrapped switches failed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error<A, B> {
+    /// The first switch failed
+    First(A),
+    /// The second switch failed
+    Second(B),
+}
+
+/// Active when both switches are active.
+///
+/// Reads short circuit like `&&`; the second switch is not read when the first is inactive.
+pub struct All<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Active when either switch is active.
+///
+/// Reads short circuit like `||`; the second switch is not read when the first is active.
+pub struct Any<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Active when exactly one of the switches is active.
+pub struct Xor<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Returns a switch that is active when both `first` and `second` are active
+pub fn all<A: InputSwitch, B: InputSwitch>(first: A, second: B) -> All<A, B> {
+    All { first, second }
+}
+
+/// Returns a switch that is active when either `first` or `second` is active
+pub fn any<A: InputSwitch, B: InputSwitch>(first: A, second: B) -> Any<A, B> {
+    Any { first, second }
+}
+
+/// Returns a switch that is active when exactly one of `first` and `second` is active
+pub fn xor<A: InputSwitch, B: InputSwitch>(first: A, second: B) -> Xor<A, B> {
+    Xor { first, second }
+}
+
+/// Returns a switch that is active when `switch` is inactive
+///
+/// # Examples
+///
+/// ```
+/// # use switch_hal::mock;
+/// use switch_hal::{InputSwitch, IntoSwitch};
+/// use switch_hal::logic::{all, not};
+/// # let guard_closed = mock::Pin::with_state(mock::State::High).into_active_high_switch();
+/// # let maintenance_key = mock::Pin::with_state(mock::State::Low).into_active_high_switch();
+/// let may_run = all(guard_closed, not(maintenance_key));
+/// assert!(may_run.is_active().unwrap());
+/// ```
+pub fn not<S: InputSwitch>(switch: S) -> Inverted<S> {
+    Inverted::new(switch)
+}
+
+impl<A, B> All<A, B> {
+    /// Consumes the combinator and returns the wrapped switches
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Any<A, B> {
+    /// Consumes the combinator and returns the wrapped switches
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Xor<A, B> {
+    /// Consumes the combinator and returns the wrapped switches
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: InputSwitch, B: InputSwitch> InputSwitch for All<A, B> {
+    type Error = Error<A::Error, B::Error>;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        Ok(self.first.is_active().map_err(Error::First)?
+            && self.second.is_active().map_err(Error::Second)?)
+    }
+}
+
+impl<A: InputSwitch, B: InputSwitch> InputSwitch for Any<A, B> {
+    type Error = Error<A::Error, B::Error>;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        Ok(self.first.is_active().map_err(Error::First)?
+            || self.second.is_active().map_err(Error::Second)?)
+    }
+}
+
+impl<A: InputSwitch, B: InputSwitch> InputSwitch for Xor<A, B> {
+    type Error = Error<A::Error, B::Error>;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        Ok(self.first.is_active().map_err(Error::First)?
+            ^ self.second.is_active().map_err(Error::Second)?)
+    }
+}
@@ -0,0 +1,148 @@
+//! Drives a multiplexed LED matrix from row and column [OutputSwitch](crate::OutputSwitch)es.
+//!
+//! One row is lit at a time. Each call to [tick](Matrix::tick) advances the scan,
+//! so it should be called from a periodic timer fast enough that the whole matrix
+//! is refreshed at least 60 times per second.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::IntoSwitch;
+//! use switch_hal::matrix::Matrix;
+//! # let row_pins = [(); 7].map(|_| mock::Pin::new());
+//! # let column_pins = [(); 5].map(|_| mock::Pin::new());
+//! let mut matrix = Matrix::new(
+//!     row_pins.map(|pin| pin.into_active_high_switch()),
+//!     column_pins.map(|pin| pin.into_active_low_switch()),
+//! );
+//! matrix.set_pixel(2, 3, true);
+//!
+//! // dim the display to half brightness
+//! matrix.set_row_timing(1, 2);
+//!
+//! // from a periodic timer
+//! matrix.tick().unwrap();
+//! ```
+use crate::bank::{self, SwitchBank};
+use crate::{OutputSwitch, SwitchState};
+
+/// Error returned by a [Matrix](Matrix)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error<R, C> {
+    /// A row could not be written
+    Row(bank::Error<R>),
+    /// A column could not be written
+    Column(bank::Error<C>),
+}
+
+/// A `ROWS` x `COLS` LED matrix with a frame buffer.
+pub struct Matrix<R, C, const ROWS: usize, const COLS: usize> {
+    rows: [R; ROWS],
+    columns: SwitchBank<[C; COLS]>,
+    frame: [[bool; COLS]; ROWS],
+    row: usize,
+    slot: u8,
+    on_ticks: u8,
+    period_ticks: u8,
+}
+
+impl<R: OutputSwitch, C: OutputSwitch, const ROWS: usize, const COLS: usize>
+    Matrix<R, C, ROWS, COLS>
+{
+    /// Constructs a new [Matrix](Matrix) with a blank frame, lighting each row for a single tick.
+    ///
+    /// `rows[0]` is the top row and `columns[0]` is the leftmost column.
+    ///
+    /// # Panics
+    /// Panics if there are no rows or more than 32 columns.
+    pub fn new(rows: [R; ROWS], columns: [C; COLS]) -> Self {
+        assert!(ROWS > 0, "Matrix needs at least one row");
+        Matrix {
+            rows,
+            columns: SwitchBank::new(columns),
+            frame: [[false; COLS]; ROWS],
+            row: ROWS - 1,
+            slot: 0,
+            on_ticks: 1,
+            period_ticks: 1,
+        }
+    }
+
+    /// Consumes the [Matrix](Matrix) and returns the underlying rows and columns
+    pub fn into_inner(self) -> ([R; ROWS], [C; COLS]) {
+        (self.rows, self.columns.into_inner())
+    }
+
+    /// Sets how many ticks each row is scanned for and how many of those ticks it is lit.
+    ///
+    /// Brightness is proportional to `on_ticks / period_ticks`.
+    /// `on_ticks` is limited to `period_ticks`, which must be at least 1.
+    pub fn set_row_timing(&mut self, on_ticks: u8, period_ticks: u8) {
+        self.period_ticks = period_ticks.max(1);
+        self.on_ticks = on_ticks.min(self.period_ticks);
+        self.slot = 0;
+    }
+
+    /// Lights or clears the pixel at column `x` of row `y`, from the next time the row is scanned
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) {
+        self.frame[y][x] = on;
+    }
+
+    /// Returns whether the pixel at column `x` of row `y` is lit in the frame buffer
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        self.frame[y][x]
+    }
+
+    /// Replaces the whole frame buffer, indexed by row then column
+    pub fn set_frame(&mut self, frame: [[bool; COLS]; ROWS]) {
+        self.frame = frame;
+    }
+
+    /// Clears the frame buffer
+    pub fn clear(&mut self) {
+        self.frame = [[false; COLS]; ROWS];
+    }
+
+    /// Advances the scan by one tick.
+    ///
+    /// At the start of a row's time slot, the previous row is turned off before the columns change,
+    /// so the new row's pattern never ghosts onto the previous row.
+    /// The row is turned off again once its on time has elapsed.
+    pub fn tick(&mut self) -> Result<(), Error<R::Error, C::Error>> {
+        if self.slot == 0 {
+            self.row_off(self.row)?;
+            self.row = (self.row + 1) % ROWS;
+            self.columns
+                .set_states(self.frame[self.row].map(SwitchState::from))
+                .map_err(Error::Column)?;
+            if self.on_ticks > 0 {
+                self.row_on(self.row)?;
+            }
+        } else if self.slot == self.on_ticks {
+            self.row_off(self.row)?;
+        }
+        self.slot = (self.slot + 1) % self.period_ticks;
+        Ok(())
+    }
+
+    /// Turns every row off
+    pub fn blank(&mut self) -> Result<(), Error<R::Error, C::Error>> {
+        for index in 0..ROWS {
+            self.row_off(index)?;
+        }
+        Ok(())
+    }
+
+    fn row_on(&mut self, index: usize) -> Result<(), Error<R::Error, C::Error>> {
+        self.rows[index]
+            .on()
+            .map_err(|error| Error::Row(bank::Error { index, error }))
+    }
+
+    fn row_off(&mut self, index: usize) -> Result<(), Error<R::Error, C::Error>> {
+        self.rows[index]
+            .off()
+            .map_err(|error| Error::Row(bank::Error { index, error }))
+    }
+}
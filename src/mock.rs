@@ -5,39 +5,188 @@
 //!
 //! This is part of the main crate so it is accessible to doctests.
 //! Otherwise, I would have created a tests/mock/mod.rs file.
+//!
+//! [RecordingPin](RecordingPin) additionally keeps a history of every access,
+//! for tests that care about the sequence of states rather than just the last one.
+//! [MockPinHandle](MockPinHandle) lets a test observe and drive a pin
+//! after it has been moved into a switch owned by the code under test.
+//! [ScriptedPin](ScriptedPin) plays back a time-varying [Script](Script), such as a bouncing button.
+//!
+//! [ExpectPin](ExpectPin) checks every call against a declared sequence of expectations.
+//!
+//! [Pin](Pin) and [MockPinHandle](MockPinHandle) can be given a [Fault](Fault)
+//! to exercise the error paths of code built on switches.
 use embedded_hal::digital::v2::toggleable;
-use embedded_hal::digital::v2::{InputPin, OutputPin, StatefulOutputPin};
+use embedded_hal::digital::v2::{InputPin, IoPin, OutputPin, PinState, StatefulOutputPin};
+
+use core::cell::Cell;
+
+use crate::clock::{Clock, Millis};
+use crate::interrupt::{Edge, InterruptPin};
+
+mod expect;
+mod fault;
+mod recording;
+mod scripted;
+mod shared;
+
+use fault::Injector;
+
+pub use expect::{Call, ExpectPin};
+pub use fault::{Calls, Fault};
+pub use recording::{Access, Event, RecordingPin};
+pub use scripted::{Script, ScriptedPin, Step};
+pub use shared::{MockPinHandle, SharedPin};
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum State {
     Low,
     High,
 }
 
+impl core::ops::Not for State {
+    type Output = State;
+
+    fn not(self) -> Self::Output {
+        match self {
+            State::Low => State::High,
+            State::High => State::Low,
+        }
+    }
+}
+
 pub struct Pin {
     state: Option<State>,
+    edge: Option<Edge>,
+    interrupt_enabled: bool,
+    interrupt_pending: bool,
+    faults: Injector,
+}
+
+impl Default for Pin {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Pin {
     pub fn new() -> Self {
-        Pin { state: None }
+        Pin {
+            state: None,
+            edge: None,
+            interrupt_enabled: false,
+            interrupt_pending: false,
+            faults: Injector::default(),
+        }
     }
 
     pub fn with_state(state: State) -> Self {
-        Pin { state: Some(state) }
+        Pin {
+            state: Some(state),
+            ..Pin::new()
+        }
+    }
+
+    /// Simulates the line being driven to `state`.
+    ///
+    /// If the interrupt is enabled and the transition matches the configured edge,
+    /// the interrupt becomes pending.
+    pub fn set_state(&mut self, state: State) {
+        let edge_matches = matches!(
+            (self.state, state, self.edge),
+            (
+                Some(State::Low),
+                State::High,
+                Some(Edge::Rising | Edge::RisingFalling)
+            ) | (
+                Some(State::High),
+                State::Low,
+                Some(Edge::Falling | Edge::RisingFalling)
+            )
+        );
+
+        if self.interrupt_enabled && edge_matches {
+            self.interrupt_pending = true;
+        }
+        self.state = Some(state);
+    }
+
+    /// Injects `fault` into subsequent calls
+    pub fn with_fault(self, fault: Fault) -> Self {
+        self.set_fault(Some(fault));
+        self
+    }
+
+    /// Replaces the injected fault, restarting its call count, or removes it with `None`
+    pub fn set_fault(&self, fault: Option<Fault>) {
+        self.faults.set(fault);
+    }
+
+    /// The edge the interrupt was last configured to trigger on
+    pub fn edge(&self) -> Option<Edge> {
+        self.edge
+    }
+
+    /// Whether the interrupt is currently enabled
+    pub fn is_interrupt_enabled(&self) -> bool {
+        self.interrupt_enabled
     }
 }
 
-type MockError = &'static str;
+/// Errors returned by the mock pins
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MockError {
+    /// The pin was read before its state was set
+    Uninitialized,
+    /// The call failed because of an injected [Fault](Fault)
+    Injected,
+}
+
+/// Manually advanced [Clock](crate::clock::Clock), counting milliseconds from `0`.
+///
+/// Mocks that take a `&MockClock` share it with the test, which advances time explicitly.
+#[derive(Default, Debug)]
+pub struct MockClock {
+    now: Cell<u64>,
+}
+
+impl MockClock {
+    /// Constructs a new [MockClock](MockClock) at `0`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current time, in milliseconds
+    pub fn millis(&self) -> u64 {
+        self.now.get()
+    }
+
+    pub fn advance(&self, millis: u64) {
+        self.now.set(self.now.get() + millis);
+    }
+
+    pub fn set(&self, millis: u64) {
+        self.now.set(millis);
+    }
+}
+
+impl Clock for MockClock {
+    type Instant = Millis;
+
+    fn now(&self) -> Self::Instant {
+        Millis(self.millis())
+    }
+}
 
 impl InputPin for Pin {
     type Error = MockError;
 
     fn is_high(&self) -> Result<bool, Self::Error> {
+        self.faults.read()?;
         match self.state {
             Some(State::High) => Ok(true),
             Some(State::Low) => Ok(false),
-            None => Err("state not set"),
+            None => Err(MockError::Uninitialized),
         }
     }
 
@@ -53,12 +202,14 @@ impl OutputPin for Pin {
     type Error = MockError;
 
     fn set_low(&mut self) -> Result<(), Self::Error> {
-        self.state = Some(State::Low);
+        self.faults.write()?;
+        self.set_state(State::Low);
         Ok(())
     }
 
     fn set_high(&mut self) -> Result<(), Self::Error> {
-        self.state = Some(State::High);
+        self.faults.write()?;
+        self.set_state(State::High);
         Ok(())
     }
 }
@@ -75,6 +226,91 @@ impl StatefulOutputPin for Pin {
 
 impl toggleable::Default for Pin {}
 
+/// Changing mode keeps the last state when becoming an input,
+/// and sets the requested state when becoming an output.
+impl IoPin<Pin, Pin> for Pin {
+    type Error = MockError;
+
+    fn into_input_pin(self) -> Result<Pin, Self::Error> {
+        Ok(self)
+    }
+
+    fn into_output_pin(mut self, state: PinState) -> Result<Pin, Self::Error> {
+        match state {
+            PinState::Low => self.set_state(State::Low),
+            PinState::High => self.set_state(State::High),
+        }
+        Ok(self)
+    }
+}
+
+impl InterruptPin for Pin {
+    fn trigger_on_edge(&mut self, edge: Edge) {
+        self.edge = Some(edge);
+    }
+
+    fn enable_interrupt(&mut self) {
+        self.interrupt_enabled = true;
+    }
+
+    fn disable_interrupt(&mut self) {
+        self.interrupt_enabled = false;
+    }
+
+    fn check_interrupt(&self) -> bool {
+        self.interrupt_pending
+    }
+
+    fn clear_interrupt_pending_bit(&mut self) {
+        self.interrupt_pending = false;
+    }
+}
+
+/// Mock implementation of [PwmPin](embedded_hal::PwmPin)
+pub struct Pwm {
+    duty: u16,
+    max_duty: u16,
+    enabled: bool,
+}
+
+impl Pwm {
+    pub fn new(max_duty: u16) -> Self {
+        Pwm {
+            duty: 0,
+            max_duty,
+            enabled: false,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl embedded_hal::PwmPin for Pwm {
+    type Duty = u16;
+
+    fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    fn get_duty(&self) -> Self::Duty {
+        self.duty
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        self.max_duty
+    }
+
+    fn set_duty(&mut self, duty: Self::Duty) {
+        self.duty = duty;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -95,7 +331,7 @@ mod test {
 
         #[test]
         fn error_when_uninitialized() {
-            let pin = Pin { state: None };
+            let pin = Pin::new();
             pin.is_high().expect_err("Expected uninitialized pin");
         }
 
@@ -157,7 +393,7 @@ mod test {
 
         #[test]
         fn error_when_uninitialized() {
-            let pin = Pin { state: None };
+            let pin = Pin::new();
             pin.is_set_high().expect_err("Expected uninitialized pin");
         }
 
@@ -205,4 +441,74 @@ mod test {
             }
         }
     }
+
+    mod faults {
+        use super::*;
+
+        #[test]
+        fn reads_fail_after_point() {
+            let pin = Pin::with_state(State::High).with_fault(Fault::after(1).reads());
+            assert_eq!(Ok(true), pin.is_high());
+            assert_eq!(Err(MockError::Injected), pin.is_high());
+            assert_eq!(Err(MockError::Injected), pin.is_set_low());
+        }
+
+        #[test]
+        fn failed_write_keeps_state() {
+            let mut pin = Pin::with_state(State::Low).with_fault(Fault::always().writes());
+            assert_eq!(Err(MockError::Injected), pin.set_high());
+            assert_eq!(Ok(true), pin.is_low());
+        }
+
+        #[test]
+        fn removing_fault() {
+            let mut pin = Pin::new().with_fault(Fault::always());
+            pin.set_fault(None);
+            assert_eq!(Ok(()), pin.set_high());
+        }
+    }
+
+    mod interrupt_pin {
+        use super::*;
+
+        #[test]
+        fn pending_on_configured_edge() {
+            let mut pin = Pin::with_state(State::Low);
+            pin.trigger_on_edge(Edge::Rising);
+            pin.enable_interrupt();
+
+            pin.set_state(State::High);
+            assert_eq!(true, pin.check_interrupt());
+        }
+
+        #[test]
+        fn not_pending_on_other_edge() {
+            let mut pin = Pin::with_state(State::High);
+            pin.trigger_on_edge(Edge::Rising);
+            pin.enable_interrupt();
+
+            pin.set_state(State::Low);
+            assert_eq!(false, pin.check_interrupt());
+        }
+
+        #[test]
+        fn not_pending_when_disabled() {
+            let mut pin = Pin::with_state(State::Low);
+            pin.trigger_on_edge(Edge::RisingFalling);
+
+            pin.set_state(State::High);
+            assert_eq!(false, pin.check_interrupt());
+        }
+
+        #[test]
+        fn clear_interrupt_pending_bit() {
+            let mut pin = Pin::with_state(State::High);
+            pin.trigger_on_edge(Edge::RisingFalling);
+            pin.enable_interrupt();
+
+            pin.set_state(State::Low);
+            pin.clear_interrupt_pending_bit();
+            assert_eq!(false, pin.check_interrupt());
+        }
+    }
 }
@@ -0,0 +1,262 @@
+use core::cell::Cell;
+
+use embedded_hal::digital::v2::{InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin};
+
+use super::MockError;
+
+/// A call to an [ExpectPin](ExpectPin)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Call {
+    SetHigh,
+    SetLow,
+    Toggle,
+    IsHigh,
+    IsLow,
+    IsSetHigh,
+    IsSetLow,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Expectation {
+    call: Call,
+    /// Returned by reads
+    value: bool,
+    fails: bool,
+}
+
+/// Mock pin that checks every call against a fixed sequence of up to `N` expectations,
+/// in the style of `embedded-hal-mock`.
+///
+/// Panics as soon as a call does not match the next expectation.
+/// Call [done](ExpectPin::done) at the end of the test to check every expectation was met.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::mock::ExpectPin;
+/// use switch_hal::{IntoSwitch, OutputSwitch, StatefulOutputSwitch};
+///
+/// let pin: ExpectPin<3> = ExpectPin::new()
+///     .expect_set_low()
+///     .expect_is_set_low(true)
+///     .expect_set_high();
+///
+/// let mut led = pin.into_active_low_switch();
+/// led.on().unwrap();
+/// if led.is_on().unwrap() {
+///     led.off().unwrap();
+/// }
+/// led.into_pin().done();
+/// ```
+pub struct ExpectPin<const N: usize> {
+    expectations: [Option<Expectation>; N],
+    len: usize,
+    next: Cell<usize>,
+}
+
+impl<const N: usize> Default for ExpectPin<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ExpectPin<N> {
+    /// Constructs a new [ExpectPin](ExpectPin) that expects no calls
+    pub fn new() -> Self {
+        ExpectPin {
+            expectations: [None; N],
+            len: 0,
+            next: Cell::new(0),
+        }
+    }
+
+    pub fn expect_set_high(self) -> Self {
+        self.expect(Call::SetHigh, false)
+    }
+
+    pub fn expect_set_low(self) -> Self {
+        self.expect(Call::SetLow, false)
+    }
+
+    pub fn expect_toggle(self) -> Self {
+        self.expect(Call::Toggle, false)
+    }
+
+    /// Expects `is_high`, which returns `value`
+    pub fn expect_is_high(self, value: bool) -> Self {
+        self.expect(Call::IsHigh, value)
+    }
+
+    /// Expects `is_low`, which returns `value`
+    pub fn expect_is_low(self, value: bool) -> Self {
+        self.expect(Call::IsLow, value)
+    }
+
+    /// Expects `is_set_high`, which returns `value`
+    pub fn expect_is_set_high(self, value: bool) -> Self {
+        self.expect(Call::IsSetHigh, value)
+    }
+
+    /// Expects `is_set_low`, which returns `value`
+    pub fn expect_is_set_low(self, value: bool) -> Self {
+        self.expect(Call::IsSetLow, value)
+    }
+
+    /// Makes the previous expectation return [MockError::Injected](MockError::Injected)
+    pub fn failing(mut self) -> Self {
+        match self.len.checked_sub(1) {
+            Some(last) => {
+                if let Some(expectation) = self.expectations[last].as_mut() {
+                    expectation.fails = true;
+                }
+            }
+            None => panic!("ExpectPin: failing() called before any expectation"),
+        }
+        self
+    }
+
+    /// Panics unless every expectation was met
+    pub fn done(&self) {
+        let next = self.next.get();
+        if let Some(Some(expectation)) = self.expectations[..self.len].get(next) {
+            panic!(
+                "ExpectPin: {} of {} expectations not met, starting with {:?}",
+                self.len - next,
+                self.len,
+                expectation.call
+            );
+        }
+    }
+
+    fn expect(mut self, call: Call, value: bool) -> Self {
+        assert!(self.len < N, "ExpectPin: more than {} expectations", N);
+        self.expectations[self.len] = Some(Expectation {
+            call,
+            value,
+            fails: false,
+        });
+        self.len += 1;
+        self
+    }
+
+    fn call(&self, call: Call) -> Result<bool, MockError> {
+        let index = self.next.get();
+        let expectation = match self.expectations[..self.len].get(index) {
+            Some(Some(expectation)) => *expectation,
+            _ => panic!(
+                "ExpectPin: unexpected call {:?}, all {} expectations were already met",
+                call, self.len
+            ),
+        };
+        assert!(
+            expectation.call == call,
+            "ExpectPin: call {} was {:?}, but expected {:?}",
+            index,
+            call,
+            expectation.call
+        );
+        self.next.set(index + 1);
+        if expectation.fails {
+            Err(MockError::Injected)
+        } else {
+            Ok(expectation.value)
+        }
+    }
+}
+
+impl<const N: usize> InputPin for ExpectPin<N> {
+    type Error = MockError;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.call(Call::IsHigh)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.call(Call::IsLow)
+    }
+}
+
+impl<const N: usize> OutputPin for ExpectPin<N> {
+    type Error = MockError;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.call(Call::SetLow).map(|_| ())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.call(Call::SetHigh).map(|_| ())
+    }
+}
+
+impl<const N: usize> StatefulOutputPin for ExpectPin<N> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.call(Call::IsSetHigh)
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        self.call(Call::IsSetLow)
+    }
+}
+
+impl<const N: usize> ToggleableOutputPin for ExpectPin<N> {
+    type Error = MockError;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.call(Call::Toggle).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{InputSwitch, IntoSwitch, ToggleableOutputSwitch};
+
+    #[test]
+    fn reads_return_expected_values() {
+        let pin: ExpectPin<2> = ExpectPin::new().expect_is_low(true).expect_is_low(false);
+        let button = pin.into_active_low_switch();
+
+        assert_eq!(true, button.is_active().unwrap());
+        assert_eq!(false, button.is_active().unwrap());
+        button.into_pin().done();
+    }
+
+    #[test]
+    fn toggle_is_a_single_call() {
+        let pin: ExpectPin<1> = ExpectPin::new().expect_toggle();
+        let mut led = pin.into_active_high_switch();
+        led.toggle().unwrap();
+
+        led.into_pin().done();
+    }
+
+    #[test]
+    fn failing_expectation_returns_error() {
+        let mut pin: ExpectPin<1> = ExpectPin::new().expect_set_high().failing();
+        assert_eq!(Err(MockError::Injected), pin.set_high());
+        pin.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "call 1 was SetLow, but expected SetHigh")]
+    fn panics_on_wrong_call() {
+        let mut pin: ExpectPin<2> = ExpectPin::new().expect_set_low().expect_set_high();
+        pin.set_low().unwrap();
+        pin.set_low().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected call IsHigh")]
+    fn panics_on_extra_call() {
+        let pin: ExpectPin<1> = ExpectPin::new();
+        let _ = pin.is_high();
+    }
+
+    #[test]
+    #[should_panic(expected = "1 of 2 expectations not met, starting with IsSetHigh")]
+    fn done_panics_on_unmet_expectations() {
+        let mut pin: ExpectPin<2> = ExpectPin::new().expect_set_low().expect_is_set_high(false);
+        pin.set_low().unwrap();
+        pin.done();
+    }
+}
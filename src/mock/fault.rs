@@ -0,0 +1,169 @@
+use core::cell::Cell;
+
+use super::MockError;
+
+/// Which calls a [Fault](Fault) counts and fails
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Calls {
+    All,
+    /// `is_high`, `is_low`, `is_set_high` and `is_set_low`
+    Reads,
+    /// `set_high` and `set_low`
+    Writes,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum When {
+    Nth(usize),
+    After(usize),
+}
+
+/// Describes which calls to a mock pin fail with [MockError::Injected](MockError::Injected).
+///
+/// Only calls matching [Calls](Calls) are counted, and counting starts when the fault is set.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::mock::{Fault, Pin, State};
+/// use switch_hal::{IntoSwitch, OutputSwitch};
+///
+/// // the second write fails, reads always succeed
+/// let pin = Pin::with_state(State::Low).with_fault(Fault::nth(2).writes());
+/// let mut led = pin.into_active_high_switch();
+/// led.on().unwrap();
+/// led.off().expect_err("Expected injected fault");
+/// led.on().unwrap();
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fault {
+    when: When,
+    calls: Calls,
+}
+
+impl Fault {
+    /// Fails only the `n`th call, counting from `1`
+    pub const fn nth(n: usize) -> Self {
+        Fault {
+            when: When::Nth(n),
+            calls: Calls::All,
+        }
+    }
+
+    /// Fails every call after the first `n`
+    pub const fn after(n: usize) -> Self {
+        Fault {
+            when: When::After(n),
+            calls: Calls::All,
+        }
+    }
+
+    /// Fails every call
+    pub const fn always() -> Self {
+        Self::after(0)
+    }
+
+    /// Only counts and fails reads
+    pub const fn reads(self) -> Self {
+        self.on(Calls::Reads)
+    }
+
+    /// Only counts and fails writes
+    pub const fn writes(self) -> Self {
+        self.on(Calls::Writes)
+    }
+
+    pub const fn on(self, calls: Calls) -> Self {
+        Fault {
+            when: self.when,
+            calls,
+        }
+    }
+}
+
+/// Fault state shared by the mock pins
+#[derive(Default, Debug)]
+pub(super) struct Injector {
+    fault: Cell<Option<Fault>>,
+    count: Cell<usize>,
+}
+
+impl Injector {
+    pub(super) fn set(&self, fault: Option<Fault>) {
+        self.fault.set(fault);
+        self.count.set(0);
+    }
+
+    pub(super) fn read(&self) -> Result<(), MockError> {
+        self.check(Calls::Reads)
+    }
+
+    pub(super) fn write(&self) -> Result<(), MockError> {
+        self.check(Calls::Writes)
+    }
+
+    fn check(&self, call: Calls) -> Result<(), MockError> {
+        let fault = match self.fault.get() {
+            Some(fault) if fault.calls == Calls::All || fault.calls == call => fault,
+            _ => return Ok(()),
+        };
+        let count = self.count.get() + 1;
+        self.count.set(count);
+        let fails = match fault.when {
+            When::Nth(n) => count == n,
+            When::After(n) => count > n,
+        };
+        if fails {
+            Err(MockError::Injected)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nth_fails_once() {
+        let injector = Injector::default();
+        injector.set(Some(Fault::nth(2)));
+
+        assert_eq!(Ok(()), injector.read());
+        assert_eq!(Err(MockError::Injected), injector.write());
+        assert_eq!(Ok(()), injector.read());
+    }
+
+    #[test]
+    fn after_fails_every_later_call() {
+        let injector = Injector::default();
+        injector.set(Some(Fault::after(1)));
+
+        assert_eq!(Ok(()), injector.write());
+        assert_eq!(Err(MockError::Injected), injector.write());
+        assert_eq!(Err(MockError::Injected), injector.read());
+    }
+
+    #[test]
+    fn filtered_calls_are_not_counted() {
+        let injector = Injector::default();
+        injector.set(Some(Fault::nth(1).reads()));
+
+        assert_eq!(Ok(()), injector.write());
+        assert_eq!(Err(MockError::Injected), injector.read());
+        assert_eq!(Ok(()), injector.write());
+    }
+
+    #[test]
+    fn setting_resets_count() {
+        let injector = Injector::default();
+        injector.set(Some(Fault::nth(1)));
+        assert_eq!(Err(MockError::Injected), injector.read());
+
+        injector.set(Some(Fault::nth(1)));
+        assert_eq!(Err(MockError::Injected), injector.read());
+        injector.set(None);
+        assert_eq!(Ok(()), injector.read());
+    }
+}
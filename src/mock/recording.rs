@@ -0,0 +1,283 @@
+use core::cell::{Cell, RefCell};
+use core::fmt;
+
+use embedded_hal::digital::v2::{InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin};
+
+use super::{MockClock, MockError, State};
+
+/// Operation recorded by a [RecordingPin](RecordingPin)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Access {
+    SetHigh,
+    SetLow,
+    Toggle,
+    /// Any of `is_high`, `is_low`, `is_set_high` or `is_set_low`
+    Read,
+}
+
+/// Entry in the history of a [RecordingPin](RecordingPin)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Event {
+    /// Position in the history, starting at `0`
+    pub sequence: usize,
+    pub access: Access,
+    /// State after a write, or the state observed by a read
+    pub state: State,
+    /// Time of the access, if the pin was given a [MockClock](MockClock)
+    pub timestamp: Option<u64>,
+}
+
+/// Mock pin that records every access in a fixed capacity history of `N` events.
+///
+/// Panics when an access would overflow the history, so pick `N` large enough for the test.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::mock::{RecordingPin, State};
+/// use switch_hal::{IntoSwitch, OutputSwitch, ToggleableOutputSwitch};
+///
+/// let pin: RecordingPin<8> = RecordingPin::with_state(State::Low);
+/// let mut led = pin.into_active_high_switch();
+/// led.on().unwrap();
+/// led.off().unwrap();
+/// led.toggle().unwrap();
+///
+/// led.into_pin().assert_transitions(&[State::High, State::Low, State::High]);
+/// ```
+pub struct RecordingPin<'a, const N: usize> {
+    state: Option<State>,
+    initial: Option<State>,
+    clock: Option<&'a MockClock>,
+    history: RefCell<[Option<Event>; N]>,
+    len: Cell<usize>,
+}
+
+impl<'a, const N: usize> Default for RecordingPin<'a, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, const N: usize> RecordingPin<'a, N> {
+    pub fn new() -> Self {
+        RecordingPin {
+            state: None,
+            initial: None,
+            clock: None,
+            history: RefCell::new([None; N]),
+            len: Cell::new(0),
+        }
+    }
+
+    pub fn with_state(state: State) -> Self {
+        RecordingPin {
+            state: Some(state),
+            initial: Some(state),
+            ..Self::new()
+        }
+    }
+
+    /// Timestamps every event with the current time of `clock`
+    pub fn with_clock(mut self, clock: &'a MockClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Every recorded event, oldest first
+    pub fn events(&self) -> impl Iterator<Item = Event> {
+        let history = *self.history.borrow();
+        IntoIterator::into_iter(history).flatten()
+    }
+
+    /// Every change of state caused by a write, oldest first.
+    ///
+    /// Writes that leave the pin in the state it was already in are not transitions.
+    pub fn transitions(&self) -> impl Iterator<Item = State> + '_ {
+        let mut previous = self.initial;
+        self.events()
+            .filter(|event| event.access != Access::Read)
+            .filter_map(move |event| {
+                let changed = previous != Some(event.state);
+                previous = Some(event.state);
+                if changed {
+                    Some(event.state)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Panics unless the pin went through exactly the `expected` transitions
+    pub fn assert_transitions(&self, expected: &[State]) {
+        let matches = self.transitions().eq(expected.iter().copied());
+        assert!(
+            matches,
+            "unexpected transitions\n  expected: {:?}\n    actual: {}",
+            expected,
+            Transitions(self)
+        );
+    }
+
+    /// Forgets every recorded event, keeping the current state
+    pub fn clear_history(&mut self) {
+        self.history = RefCell::new([None; N]);
+        self.len.set(0);
+        self.initial = self.state;
+    }
+
+    fn record(&self, access: Access, state: State) {
+        let sequence = self.len.get();
+        assert!(sequence < N, "RecordingPin history is full ({} events)", N);
+        self.history.borrow_mut()[sequence] = Some(Event {
+            sequence,
+            access,
+            state,
+            timestamp: self.clock.map(MockClock::millis),
+        });
+        self.len.set(sequence + 1);
+    }
+
+    fn write(&mut self, access: Access, state: State) -> Result<(), MockError> {
+        self.state = Some(state);
+        self.record(access, state);
+        Ok(())
+    }
+
+    fn observe(&self) -> Result<State, MockError> {
+        let state = self.state.ok_or(MockError::Uninitialized)?;
+        self.record(Access::Read, state);
+        Ok(state)
+    }
+}
+
+/// Formats transitions like a slice, without allocating
+struct Transitions<'p, 'a, const N: usize>(&'p RecordingPin<'a, N>);
+
+impl<'p, 'a, const N: usize> fmt::Display for Transitions<'p, 'a, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.transitions()).finish()
+    }
+}
+
+impl<'a, const N: usize> InputPin for RecordingPin<'a, N> {
+    type Error = MockError;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.observe()? == State::High)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.observe()? == State::Low)
+    }
+}
+
+impl<'a, const N: usize> OutputPin for RecordingPin<'a, N> {
+    type Error = MockError;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.write(Access::SetLow, State::Low)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.write(Access::SetHigh, State::High)
+    }
+}
+
+impl<'a, const N: usize> StatefulOutputPin for RecordingPin<'a, N> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.is_high()
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        self.is_low()
+    }
+}
+
+/// Recorded as a single [Toggle](Access::Toggle), rather than a read followed by a write
+impl<'a, const N: usize> ToggleableOutputPin for RecordingPin<'a, N> {
+    type Error = MockError;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        let state = !self.state.ok_or(MockError::Uninitialized)?;
+        self.write(Access::Toggle, state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn records_writes_and_reads_in_order() {
+        let mut pin: RecordingPin<4> = RecordingPin::new();
+        pin.set_high().unwrap();
+        assert_eq!(true, pin.is_high().unwrap());
+        pin.toggle().unwrap();
+
+        let accesses: [_; 3] = [Access::SetHigh, Access::Read, Access::Toggle];
+        assert!(pin.events().map(|e| e.access).eq(accesses.iter().copied()));
+        assert!(pin.events().map(|e| e.sequence).eq(0..3));
+        assert_eq!(State::Low, pin.events().last().unwrap().state);
+    }
+
+    #[test]
+    fn uninitialized_reads_are_not_recorded() {
+        let pin: RecordingPin<1> = RecordingPin::new();
+        pin.is_low().expect_err("Expected uninitialized pin");
+        assert_eq!(0, pin.events().count());
+    }
+
+    #[test]
+    fn repeated_writes_are_not_transitions() {
+        let mut pin: RecordingPin<4> = RecordingPin::with_state(State::Low);
+        pin.set_low().unwrap();
+        pin.set_high().unwrap();
+        pin.set_high().unwrap();
+        pin.set_low().unwrap();
+
+        pin.assert_transitions(&[State::High, State::Low]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected transitions")]
+    fn assert_transitions_panics_on_mismatch() {
+        let mut pin: RecordingPin<2> = RecordingPin::with_state(State::Low);
+        pin.set_high().unwrap();
+
+        pin.assert_transitions(&[State::Low]);
+    }
+
+    #[test]
+    #[should_panic(expected = "history is full")]
+    fn panics_when_full() {
+        let mut pin: RecordingPin<1> = RecordingPin::new();
+        pin.set_high().unwrap();
+        pin.set_low().unwrap();
+    }
+
+    #[test]
+    fn clear_history_keeps_state() {
+        let mut pin: RecordingPin<2> = RecordingPin::new();
+        pin.set_high().unwrap();
+        pin.clear_history();
+        pin.set_high().unwrap();
+
+        assert_eq!(1, pin.events().count());
+        pin.assert_transitions(&[]);
+    }
+
+    #[test]
+    fn timestamps_from_clock() {
+        let clock = MockClock::new();
+        let mut pin: RecordingPin<2> = RecordingPin::new().with_clock(&clock);
+        pin.set_high().unwrap();
+        clock.advance(5);
+        pin.set_low().unwrap();
+
+        assert!(pin
+            .events()
+            .map(|e| e.timestamp)
+            .eq([Some(0), Some(5)].iter().copied()));
+    }
+}
@@ -0,0 +1,243 @@
+use core::cell::Cell;
+
+use embedded_hal::digital::v2::InputPin;
+
+use super::{MockClock, MockError, State};
+
+/// A change of state at a point in time
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Step {
+    pub at: u64,
+    pub state: State,
+}
+
+/// Time-varying input signal of up to `N` steps, built in chronological order.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::mock::{Script, State};
+///
+/// // a press at tick 10 that bounces twice over 4 ticks, released cleanly at tick 50
+/// let script: Script<8> = Script::new(State::High)
+///     .bouncy_edge(10, State::Low, 2, 4)
+///     .step(50, State::High);
+///
+/// assert_eq!(State::High, script.state_at(9));
+/// assert_eq!(State::Low, script.state_at(10));
+/// assert_eq!(State::High, script.state_at(11));
+/// assert_eq!(State::Low, script.state_at(14));
+/// assert_eq!(State::High, script.state_at(50));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Script<const N: usize> {
+    initial: State,
+    steps: [Step; N],
+    len: usize,
+}
+
+impl<const N: usize> Script<N> {
+    /// Constructs a new [Script](Script) that stays in `initial` until the first step
+    pub fn new(initial: State) -> Self {
+        Script {
+            initial,
+            steps: [Step {
+                at: 0,
+                state: initial,
+            }; N],
+            len: 0,
+        }
+    }
+
+    /// Changes to `state` at time `at`.
+    ///
+    /// Panics if `at` is before the previous step or the script is full.
+    pub fn step(mut self, at: u64, state: State) -> Self {
+        assert!(self.len < N, "Script is full ({} steps)", N);
+        assert!(
+            self.len == 0 || self.steps[self.len - 1].at <= at,
+            "Script steps must be in chronological order"
+        );
+        self.steps[self.len] = Step { at, state };
+        self.len += 1;
+        self
+    }
+
+    /// Changes to `state` at time `at`, then bounces back to the previous state and returns
+    /// `bounces` times, evenly spaced, settling on `state` at `at + duration`.
+    ///
+    /// Adds `1 + 2 * bounces` steps.
+    pub fn bouncy_edge(mut self, at: u64, state: State, bounces: u32, duration: u64) -> Self {
+        let previous = !state;
+        self = self.step(at, state);
+        if bounces == 0 {
+            return self;
+        }
+        let interval = duration / (2 * u64::from(bounces));
+        for bounce in 1..u64::from(bounces) {
+            self = self
+                .step(at + interval * (2 * bounce - 1), previous)
+                .step(at + interval * 2 * bounce, state);
+        }
+        self.step(at + duration - interval, previous)
+            .step(at + duration, state)
+    }
+
+    /// The state of the signal at time `now`
+    pub fn state_at(&self, now: u64) -> State {
+        self.steps[..self.len]
+            .iter()
+            .take_while(|step| step.at <= now)
+            .last()
+            .map_or(self.initial, |step| step.state)
+    }
+
+    /// Time of the last step, after which the signal no longer changes
+    pub fn end(&self) -> u64 {
+        self.steps[..self.len].last().map_or(0, |step| step.at)
+    }
+
+    /// Every step, in chronological order
+    pub fn steps(&self) -> &[Step] {
+        &self.steps[..self.len]
+    }
+}
+
+enum Timebase<'a> {
+    Clock(&'a MockClock),
+    Reads(Cell<u64>),
+}
+
+/// Mock input pin that plays back a [Script](Script).
+///
+/// By default every read is one tick, so the first read sees tick `0`, the second tick `1`, and so on.
+/// With [with_clock](ScriptedPin::with_clock), reads see the current time of a [MockClock](MockClock),
+/// so steps are in milliseconds, and the test advances time explicitly.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::mock::{MockClock, Script, ScriptedPin, State};
+/// use switch_hal::{InputSwitch, IntoSwitch};
+///
+/// let clock = MockClock::new();
+/// let script: Script<2> = Script::new(State::High).step(5, State::Low);
+/// let button = ScriptedPin::new(script).with_clock(&clock).into_active_low_switch();
+///
+/// assert_eq!(false, button.is_active().unwrap());
+/// clock.advance(5);
+/// assert_eq!(true, button.is_active().unwrap());
+/// ```
+pub struct ScriptedPin<'a, const N: usize> {
+    script: Script<N>,
+    time: Timebase<'a>,
+}
+
+impl<'a, const N: usize> ScriptedPin<'a, N> {
+    pub fn new(script: Script<N>) -> Self {
+        ScriptedPin {
+            script,
+            time: Timebase::Reads(Cell::new(0)),
+        }
+    }
+
+    /// Reads the state at the current time of `clock`, instead of advancing on every read
+    pub fn with_clock(mut self, clock: &'a MockClock) -> Self {
+        self.time = Timebase::Clock(clock);
+        self
+    }
+
+    /// Whether the script has no further steps after the current time
+    pub fn is_finished(&self) -> bool {
+        let now = match &self.time {
+            Timebase::Clock(clock) => clock.millis(),
+            Timebase::Reads(reads) => reads.get(),
+        };
+        now >= self.script.end()
+    }
+
+    fn read(&self) -> State {
+        let now = match &self.time {
+            Timebase::Clock(clock) => clock.millis(),
+            Timebase::Reads(reads) => {
+                let now = reads.get();
+                reads.set(now + 1);
+                now
+            }
+        };
+        self.script.state_at(now)
+    }
+}
+
+impl<'a, const N: usize> InputPin for ScriptedPin<'a, N> {
+    type Error = MockError;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.read() == State::High)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.read() == State::Low)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_read_is_a_tick() {
+        let script: Script<2> = Script::new(State::Low)
+            .step(1, State::High)
+            .step(2, State::Low);
+        let pin = ScriptedPin::new(script);
+
+        assert_eq!(true, pin.is_low().unwrap());
+        assert_eq!(false, pin.is_low().unwrap());
+        assert_eq!(false, pin.is_high().unwrap());
+        assert_eq!(true, pin.is_finished());
+    }
+
+    #[test]
+    fn clock_reads_do_not_advance() {
+        let clock = MockClock::new();
+        let script: Script<1> = Script::new(State::Low).step(1, State::High);
+        let pin = ScriptedPin::new(script).with_clock(&clock);
+
+        assert_eq!(true, pin.is_low().unwrap());
+        assert_eq!(true, pin.is_low().unwrap());
+        assert_eq!(false, pin.is_finished());
+        clock.advance(1);
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn bouncy_edge_steps() {
+        let script: Script<5> = Script::new(State::Low).bouncy_edge(10, State::High, 2, 8);
+        let expected = [
+            (10, State::High),
+            (12, State::Low),
+            (14, State::High),
+            (16, State::Low),
+            (18, State::High),
+        ];
+
+        let steps = script.steps().iter().map(|step| (step.at, step.state));
+        assert!(steps.eq(expected.iter().copied()));
+    }
+
+    #[test]
+    fn bouncy_edge_without_bounces_is_clean() {
+        let script: Script<1> = Script::new(State::Low).bouncy_edge(3, State::High, 0, 8);
+        assert_eq!(3, script.end());
+        assert_eq!(State::High, script.state_at(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "chronological order")]
+    fn steps_must_be_in_order() {
+        let _: Script<2> = Script::new(State::Low)
+            .step(5, State::High)
+            .step(4, State::Low);
+    }
+}
@@ -0,0 +1,177 @@
+use core::cell::Cell;
+
+use embedded_hal::digital::v2::{toggleable, InputPin, OutputPin, StatefulOutputPin};
+
+use super::{Fault, Injector, MockError, State};
+
+/// Owns the state of a [SharedPin](SharedPin), so a test can keep observing and driving
+/// the pin after it has been moved into a [Switch](crate::Switch) owned by the code under test.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::mock::{MockPinHandle, SharedPin, State};
+/// use switch_hal::{ActiveHigh, InputSwitch, IntoSwitch, OutputSwitch, Switch};
+///
+/// struct Lamp<'a> {
+///     button: Switch<SharedPin<'a>, ActiveHigh>,
+///     led: Switch<SharedPin<'a>, ActiveHigh>,
+/// }
+///
+/// let button = MockPinHandle::with_state(State::Low);
+/// let led = MockPinHandle::new();
+/// let mut lamp = Lamp {
+///     button: button.pin().into_active_high_switch(),
+///     led: led.pin().into_active_high_switch(),
+/// };
+///
+/// button.set_state(State::High);
+/// if lamp.button.is_active().unwrap() {
+///     lamp.led.on().unwrap();
+/// }
+/// assert_eq!(Some(State::High), led.state());
+/// ```
+#[derive(Default, Debug)]
+pub struct MockPinHandle {
+    state: Cell<Option<State>>,
+    faults: Injector,
+}
+
+impl MockPinHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_state(state: State) -> Self {
+        MockPinHandle {
+            state: Cell::new(Some(state)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a pin that shares its state with this handle.
+    ///
+    /// Any number of pins may be taken from the same handle.
+    pub fn pin(&self) -> SharedPin<'_> {
+        SharedPin { handle: self }
+    }
+
+    /// The current state, or `None` if it was never set
+    pub fn state(&self) -> Option<State> {
+        self.state.get()
+    }
+
+    /// Simulates the line being driven to `state`
+    pub fn set_state(&self, state: State) {
+        self.state.set(Some(state));
+    }
+
+    /// Injects `fault` into subsequent calls on every pin taken from this handle,
+    /// or removes it with `None`
+    pub fn set_fault(&self, fault: Option<Fault>) {
+        self.faults.set(fault);
+    }
+}
+
+/// Mock pin whose state lives in a [MockPinHandle](MockPinHandle)
+pub struct SharedPin<'a> {
+    handle: &'a MockPinHandle,
+}
+
+impl<'a> InputPin for SharedPin<'a> {
+    type Error = MockError;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.handle.faults.read()?;
+        match self.handle.state() {
+            Some(state) => Ok(state == State::High),
+            None => Err(MockError::Uninitialized),
+        }
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl<'a> OutputPin for SharedPin<'a> {
+    type Error = MockError;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.handle.faults.write()?;
+        self.handle.set_state(State::Low);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.handle.faults.write()?;
+        self.handle.set_state(State::High);
+        Ok(())
+    }
+}
+
+impl<'a> StatefulOutputPin for SharedPin<'a> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.is_high()
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        self.is_low()
+    }
+}
+
+impl<'a> toggleable::Default for SharedPin<'a> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        InputSwitch, IntoSwitch, OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch,
+    };
+
+    #[test]
+    fn input_follows_handle() {
+        let handle = MockPinHandle::with_state(State::High);
+        let button = handle.pin().into_active_low_switch();
+        assert_eq!(false, button.is_active().unwrap());
+
+        handle.set_state(State::Low);
+        assert_eq!(true, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn output_is_observable() {
+        let handle = MockPinHandle::new();
+        let mut led = handle.pin().into_active_low_switch();
+        led.on().unwrap();
+
+        assert_eq!(Some(State::Low), handle.state());
+    }
+
+    #[test]
+    fn stateful_and_toggleable() {
+        let handle = MockPinHandle::with_state(State::Low);
+        let mut led = handle.pin().into_active_high_switch();
+        led.toggle().unwrap();
+
+        assert_eq!(true, led.is_on().unwrap());
+        assert_eq!(Some(State::High), handle.state());
+    }
+
+    #[test]
+    fn faults_injected_through_handle() {
+        let handle = MockPinHandle::with_state(State::Low);
+        let mut led = handle.pin().into_active_high_switch();
+        handle.set_fault(Some(Fault::always().writes()));
+
+        assert_eq!(Err(MockError::Injected), led.on());
+        assert_eq!(Some(State::Low), handle.state());
+    }
+
+    #[test]
+    fn error_when_uninitialized() {
+        let handle = MockPinHandle::new();
+        let button = handle.pin().into_active_high_switch();
+        button.is_active().expect_err("Expected uninitialized pin");
+    }
+}
@@ -1,6 +1,9 @@
+use core::convert::Infallible;
+
 use embedded_hal::digital::v2::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
+use embedded_hal::PwmPin;
 
-use crate::{ActiveHigh, ActiveLow, OutputSwitch, Switch, StatefulOutputSwitch, ToggleableOutputSwitch};
+use crate::{ActiveHigh, ActiveLow, DimmableOutputSwitch, OutputSwitch, Switch, SwitchState, StatefulOutputSwitch, ToggleableOutputSwitch};
 
 impl<T: OutputPin> OutputSwitch for Switch<T, ActiveHigh> {
     type Error = <T as OutputPin>::Error;
@@ -63,3 +66,76 @@ impl<T: OutputPin + StatefulOutputPin> StatefulOutputSwitch
         self.pin.is_set_low()
     }
 }
+
+/// Scales `brightness` from `0..=255` to `0..=max_duty`
+fn duty(brightness: u8, max_duty: u16) -> u16 {
+    (u32::from(max_duty) * u32::from(brightness) / 255) as u16
+}
+
+impl<T: PwmPin<Duty = u16>> DimmableOutputSwitch for Switch<T, ActiveHigh> {
+    type Error = Infallible;
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Self::Error> {
+        let max_duty = self.pin.get_max_duty();
+        self.pin.set_duty(duty(brightness, max_duty));
+        Ok(())
+    }
+}
+
+impl<T: PwmPin<Duty = u16>> DimmableOutputSwitch for Switch<T, ActiveLow> {
+    type Error = Infallible;
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Self::Error> {
+        let max_duty = self.pin.get_max_duty();
+        self.pin.set_duty(max_duty - duty(brightness, max_duty));
+        Ok(())
+    }
+}
+
+impl<S: OutputSwitch + ?Sized> OutputSwitch for &mut S {
+    type Error = S::Error;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        (**self).on()
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        (**self).off()
+    }
+
+    fn set_state(&mut self, state: SwitchState) -> Result<(), Self::Error> {
+        (**self).set_state(state)
+    }
+}
+
+impl<S: ToggleableOutputSwitch + ?Sized> ToggleableOutputSwitch for &mut S {
+    type Error = S::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        (**self).toggle()
+    }
+}
+
+impl<S: StatefulOutputSwitch + ?Sized> StatefulOutputSwitch for &mut S {
+    type Error = S::Error;
+
+    fn is_on(&mut self) -> Result<bool, Self::Error> {
+        (**self).is_on()
+    }
+
+    fn is_off(&mut self) -> Result<bool, Self::Error> {
+        (**self).is_off()
+    }
+
+    fn state(&mut self) -> Result<SwitchState, Self::Error> {
+        (**self).state()
+    }
+}
+
+impl<S: DimmableOutputSwitch + ?Sized> DimmableOutputSwitch for &mut S {
+    type Error = S::Error;
+
+    fn set_brightness(&mut self, brightness: u8) -> Result<(), Self::Error> {
+        (**self).set_brightness(brightness)
+    }
+}
@@ -0,0 +1,100 @@
+use embedded_hal::digital::v2::{InputPin, OutputPin, ToggleableOutputPin};
+
+use crate::{InputSwitch, OutputSwitch, Switch, ToggleableOutputSwitch};
+
+/// Adapter that exposes any [InputSwitch](trait.InputSwitch.html) or [OutputSwitch](trait.OutputSwitch.html)
+/// through the `embedded-hal` digital traits, where "high" means "active".
+///
+/// This allows a logical signal to be handed to third party drivers that consume
+/// an [OutputPin](embedded_hal::digital::v2::OutputPin) or [InputPin](embedded_hal::digital::v2::InputPin),
+/// regardless of how the signal is wired.
+///
+/// [Switch](struct.Switch.html) implements [InputPin](embedded_hal::digital::v2::InputPin) directly,
+/// so for inputs the adapter is only needed for other switches, such as [Inverted](struct.Inverted.html).
+/// [OutputPin](embedded_hal::digital::v2::OutputPin) and
+/// [ToggleableOutputPin](embedded_hal::digital::v2::ToggleableOutputPin) are only implemented here,
+/// because on [Switch](struct.Switch.html) their `set_state` and `toggle` would be ambiguous with
+/// [OutputSwitch::set_state](trait.OutputSwitch.html#method.set_state) and
+/// [ToggleableOutputSwitch::toggle](trait.ToggleableOutputSwitch.html#tymethod.toggle)
+/// whenever the `embedded-hal` traits are in scope.
+///
+/// # Notes
+/// [StatefulOutputPin](embedded_hal::digital::v2::StatefulOutputPin) is not implemented,
+/// because [StatefulOutputSwitch](trait.StatefulOutputSwitch.html) requires mutable access to read the state.
+///
+/// # Examples
+///
+/// ```
+/// # use switch_hal::mock;
+/// use embedded_hal::digital::v2::OutputPin;
+/// use switch_hal::{IntoSwitch, SwitchPin};
+/// # let pin = mock::Pin::new();
+/// # fn driver<P: OutputPin>(mut chip_select: P) { chip_select.set_high().ok(); }
+/// let chip_select = pin.into_active_low_switch();
+/// // the driver asserts chip select by setting it "high", which drives the line low
+/// driver(SwitchPin::new(chip_select));
+/// ```
+pub struct SwitchPin<S> {
+    switch: S,
+}
+
+impl<S> SwitchPin<S> {
+    /// Wraps `switch` so it can be used as an `embedded-hal` pin
+    pub fn new(switch: S) -> Self {
+        SwitchPin { switch }
+    }
+
+    /// Consumes the [SwitchPin](struct.SwitchPin.html) and returns the wrapped switch
+    pub fn into_inner(self) -> S {
+        self.switch
+    }
+}
+
+impl<S: OutputSwitch> OutputPin for SwitchPin<S> {
+    type Error = <S as OutputSwitch>::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.switch.off()
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.switch.on()
+    }
+}
+
+impl<S: ToggleableOutputSwitch> ToggleableOutputPin for SwitchPin<S> {
+    type Error = <S as ToggleableOutputSwitch>::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.switch.toggle()
+    }
+}
+
+impl<S: InputSwitch> InputPin for SwitchPin<S> {
+    type Error = <S as InputSwitch>::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.switch.is_active()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.switch.is_active().map(|active| !active)
+    }
+}
+
+/// A [Switch](struct.Switch.html) is an [InputPin](embedded_hal::digital::v2::InputPin)
+/// that reads "high" when the switch is active, regardless of its `ActiveLevel`.
+impl<T, ActiveLevel> InputPin for Switch<T, ActiveLevel>
+where
+    Switch<T, ActiveLevel>: InputSwitch,
+{
+    type Error = <Self as InputSwitch>::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.is_active()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.is_active().map(|active| !active)
+    }
+}
@@ -0,0 +1,325 @@
+//! Splits a port, such as a shift register chain or GPIO expander, into per-pin handles.
+//!
+//! A [Port](Port) owns a [OutputPortDriver](OutputPortDriver) or [InputPortDriver](InputPortDriver)
+//! and caches the state of every pin, so individual pins can be changed without
+//! read-modify-write races on the device.
+//! The handles it hands out implement the `embedded-hal` digital traits,
+//! so they can be wrapped in a [Switch](crate::Switch) with the appropriate `ActiveLevel`.
+//!
+//! Input handles of a port whose driver implements [InterruptPortDriver](InterruptPortDriver)
+//! also implement [InterruptPin](crate::interrupt::InterruptPin), so they can be configured
+//! through [InterruptSwitch](crate::interrupt::InterruptSwitch) like any other input.
+//! Configuration is cached until [Port::configure_interrupts](Port::configure_interrupts) is called,
+//! and pending interrupts are read from the device by [Port::service_interrupts](Port::service_interrupts).
+//!
+//! Handles borrow the port, which uses a [RefCell](core::cell::RefCell) internally,
+//! so the port and its handles must not be shared between interrupt contexts.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! # use switch_hal::port::OutputPortDriver;
+//! # struct Leds;
+//! # impl OutputPortDriver for Leds {
+//! #     type Error = ();
+//! #     fn write(&mut self, outputs: u32) -> Result<(), ()> { Ok(()) }
+//! # }
+//! # let driver = Leds;
+//! use switch_hal::{IntoSwitch, OutputSwitch};
+//! use switch_hal::port::{Latch, Port};
+//!
+//! let port = Port::new(driver);
+//! let mut green = port.output(0).into_active_high_switch();
+//! let mut red = port.output(1).into_active_low_switch();
+//!
+//! // write both LEDs to the device at once
+//! port.set_latch(Latch::Deferred);
+//! green.on().unwrap();
+//! red.off().unwrap();
+//! port.flush().unwrap();
+//! ```
+use core::cell::RefCell;
+
+use embedded_hal::digital::v2::{toggleable, InputPin, OutputPin, StatefulOutputPin};
+
+use crate::interrupt::{Edge, InterruptPin};
+
+/// A device with up to 32 outputs that are written all at once
+pub trait OutputPortDriver {
+    type Error;
+
+    /// Writes every output, where bit `i` is pin `i` and a set bit drives the pin high
+    fn write(&mut self, outputs: u32) -> Result<(), Self::Error>;
+}
+
+/// A device with up to 32 inputs that are read all at once
+pub trait InputPortDriver {
+    type Error;
+
+    /// Reads every input, where bit `i` is pin `i` and a set bit means the pin is high
+    fn read(&mut self) -> Result<u32, Self::Error>;
+}
+
+/// Interrupt configuration of every pin of an [InterruptPortDriver](InterruptPortDriver)
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct InterruptConfig {
+    /// Pins that raise an interrupt
+    pub enabled: u32,
+    /// Pins that interrupt when they go high
+    pub rising: u32,
+    /// Pins that interrupt when they go low
+    pub falling: u32,
+}
+
+/// Interrupt status read from an [InterruptPortDriver](InterruptPortDriver)
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct InterruptStatus {
+    /// Pins that raised the interrupt
+    pub pending: u32,
+    /// State of the inputs when the interrupt was raised, only used for pins in `pending`
+    pub captured: u32,
+}
+
+/// A device that can raise an interrupt when its inputs change
+pub trait InterruptPortDriver: InputPortDriver {
+    /// Writes the interrupt configuration of every pin
+    fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Self::Error>;
+
+    /// Reads which pins raised the interrupt, acknowledging it on the device
+    fn read_interrupts(&mut self) -> Result<InterruptStatus, Self::Error>;
+}
+
+/// When changes to an output are written to the device
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Latch {
+    /// Every change is written immediately
+    Immediate,
+    /// Changes are cached until [Port::flush](Port::flush) is called
+    Deferred,
+}
+
+struct State<D> {
+    driver: D,
+    outputs: u32,
+    inputs: u32,
+    latch: Latch,
+    interrupts: InterruptConfig,
+    pending: u32,
+}
+
+/// Shares a port driver between per-pin handles
+pub struct Port<D> {
+    state: RefCell<State<D>>,
+}
+
+impl<D> Port<D> {
+    /// Constructs a new [Port](Port) in [Immediate](Latch::Immediate) mode,
+    /// assuming every output is low and every input is low until [refresh](Port::refresh) is called
+    pub fn new(driver: D) -> Self {
+        Self::with_outputs(driver, 0)
+    }
+
+    /// Constructs a new [Port](Port) in [Immediate](Latch::Immediate) mode,
+    /// with the output cache initialized to `outputs`.
+    ///
+    /// Nothing is written until an output changes or [flush](Port::flush) is called.
+    pub fn with_outputs(driver: D, outputs: u32) -> Self {
+        Port {
+            state: RefCell::new(State {
+                driver,
+                outputs,
+                inputs: 0,
+                latch: Latch::Immediate,
+                interrupts: InterruptConfig::default(),
+                pending: 0,
+            }),
+        }
+    }
+
+    /// Consumes the [Port](Port) and returns the underlying driver
+    pub fn into_inner(self) -> D {
+        self.state.into_inner().driver
+    }
+
+    /// Selects when output changes are written to the device
+    pub fn set_latch(&self, latch: Latch) {
+        self.state.borrow_mut().latch = latch;
+    }
+
+    /// Returns the cached state of every output
+    pub fn outputs(&self) -> u32 {
+        self.state.borrow().outputs
+    }
+
+    /// Returns the last snapshot of every input
+    pub fn inputs(&self) -> u32 {
+        self.state.borrow().inputs
+    }
+
+    /// Returns a handle to output pin `bit`
+    ///
+    /// # Panics
+    /// Panics if `bit` is not less than 32.
+    pub fn output(&self, bit: u8) -> OutputBit<'_, D> {
+        assert!(bit < 32, "Port supports at most 32 pins");
+        OutputBit {
+            port: self,
+            mask: 1 << bit,
+        }
+    }
+
+    /// Returns a handle to input pin `bit`
+    ///
+    /// # Panics
+    /// Panics if `bit` is not less than 32.
+    pub fn input(&self, bit: u8) -> InputBit<'_, D> {
+        assert!(bit < 32, "Port supports at most 32 pins");
+        InputBit {
+            port: self,
+            mask: 1 << bit,
+        }
+    }
+}
+
+impl<D: OutputPortDriver> Port<D> {
+    /// Writes the cached outputs to the device
+    pub fn flush(&self) -> Result<(), D::Error> {
+        let mut state = self.state.borrow_mut();
+        let outputs = state.outputs;
+        state.driver.write(outputs)
+    }
+
+    fn set(&self, mask: u32, high: bool) -> Result<(), D::Error> {
+        let mut state = self.state.borrow_mut();
+        let outputs = update(state.outputs, mask, high);
+        // only cache outputs the device accepted, so the cache never disagrees with the pins
+        if state.latch == Latch::Immediate {
+            state.driver.write(outputs)?;
+        }
+        state.outputs = outputs;
+        Ok(())
+    }
+}
+
+impl<D: InputPortDriver> Port<D> {
+    /// Reads a new snapshot of every input from the device
+    pub fn refresh(&self) -> Result<(), D::Error> {
+        let mut state = self.state.borrow_mut();
+        state.inputs = state.driver.read()?;
+        Ok(())
+    }
+}
+
+impl<D: InterruptPortDriver> Port<D> {
+    /// Writes the cached interrupt configuration, set through the input handles, to the device
+    pub fn configure_interrupts(&self) -> Result<(), D::Error> {
+        let mut state = self.state.borrow_mut();
+        let config = state.interrupts;
+        state.driver.configure_interrupts(config)
+    }
+
+    /// Reads pending interrupts from the device, typically from the interrupt handler.
+    ///
+    /// The input snapshot of every pin that raised the interrupt is replaced by its captured state.
+    /// Other inputs keep their snapshot, as devices may not capture pins that did not interrupt.
+    /// Returns every pin with a pending interrupt,
+    /// including ones not yet cleared through their handle.
+    pub fn service_interrupts(&self) -> Result<u32, D::Error> {
+        let mut state = self.state.borrow_mut();
+        let status = state.driver.read_interrupts()?;
+        state.pending |= status.pending;
+        state.inputs = (state.inputs & !status.pending) | (status.captured & status.pending);
+        Ok(state.pending)
+    }
+}
+
+/// Handle to a single output of a [Port](Port)
+pub struct OutputBit<'a, D> {
+    port: &'a Port<D>,
+    mask: u32,
+}
+
+impl<'a, D: OutputPortDriver> OutputPin for OutputBit<'a, D> {
+    type Error = D::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.port.set(self.mask, false)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.port.set(self.mask, true)
+    }
+}
+
+/// Reads the cached output, so it never communicates with the device
+impl<'a, D: OutputPortDriver> StatefulOutputPin for OutputBit<'a, D> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.port.outputs() & self.mask != 0)
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.port.outputs() & self.mask == 0)
+    }
+}
+
+impl<'a, D: OutputPortDriver> toggleable::Default for OutputBit<'a, D> {}
+
+/// Handle to a single input of a [Port](Port)
+///
+/// Reads the last snapshot taken by [Port::refresh](Port::refresh),
+/// so reading many inputs only communicates with the device once.
+pub struct InputBit<'a, D> {
+    port: &'a Port<D>,
+    mask: u32,
+}
+
+impl<'a, D: InputPortDriver> InputPin for InputBit<'a, D> {
+    type Error = D::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.port.inputs() & self.mask != 0)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.port.inputs() & self.mask == 0)
+    }
+}
+
+/// Changes are cached until [Port::configure_interrupts](Port::configure_interrupts) is called
+impl<'a, D: InterruptPortDriver> InterruptPin for InputBit<'a, D> {
+    fn trigger_on_edge(&mut self, edge: Edge) {
+        let mut state = self.port.state.borrow_mut();
+        let (rising, falling) = match edge {
+            Edge::Rising => (true, false),
+            Edge::Falling => (false, true),
+            Edge::RisingFalling => (true, true),
+        };
+        state.interrupts.rising = update(state.interrupts.rising, self.mask, rising);
+        state.interrupts.falling = update(state.interrupts.falling, self.mask, falling);
+    }
+
+    fn enable_interrupt(&mut self) {
+        self.port.state.borrow_mut().interrupts.enabled |= self.mask;
+    }
+
+    fn disable_interrupt(&mut self) {
+        self.port.state.borrow_mut().interrupts.enabled &= !self.mask;
+    }
+
+    fn check_interrupt(&self) -> bool {
+        self.port.state.borrow().pending & self.mask != 0
+    }
+
+    fn clear_interrupt_pending_bit(&mut self) {
+        self.port.state.borrow_mut().pending &= !self.mask;
+    }
+}
+
+fn update(bits: u32, mask: u32, set: bool) -> u32 {
+    if set {
+        bits | mask
+    } else {
+        bits & !mask
+    }
+}
@@ -0,0 +1,195 @@
+//! RGB and bicolor LEDs, where each color channel is its own switch.
+//!
+//! Channels may have different `ActiveLevel`s, so common anode parts are built from
+//! [ActiveLow](crate::ActiveLow) switches and common cathode parts from [ActiveHigh](crate::ActiveHigh) switches.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::IntoSwitch;
+//! use switch_hal::rgb::{Color, RgbLed};
+//! # let (r, g, b) = (mock::Pin::new(), mock::Pin::new(), mock::Pin::new());
+//! // common anode
+//! let mut status = RgbLed::new(
+//!     r.into_active_low_switch(),
+//!     g.into_active_low_switch(),
+//!     b.into_active_low_switch(),
+//! );
+//! status.set_color(Color::Cyan).unwrap();
+//! ```
+use core::convert::Infallible;
+
+use crate::{DimmableOutputSwitch, OutputSwitch};
+
+/// Colors that can be made by turning the channels of an [RgbLed](RgbLed) fully on or off
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    Off,
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    Cyan,
+    Magenta,
+    White,
+}
+
+impl Color {
+    /// Returns whether the red, green and blue channels are on
+    pub fn channels(self) -> (bool, bool, bool) {
+        match self {
+            Color::Off => (false, false, false),
+            Color::Red => (true, false, false),
+            Color::Green => (false, true, false),
+            Color::Blue => (false, false, true),
+            Color::Yellow => (true, true, false),
+            Color::Cyan => (false, true, true),
+            Color::Magenta => (true, false, true),
+            Color::White => (true, true, true),
+        }
+    }
+}
+
+/// Colors that can be made by turning the channels of a [BicolorLed](BicolorLed) fully on or off
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Bicolor {
+    Off,
+    Red,
+    Green,
+    Yellow,
+}
+
+impl Bicolor {
+    /// Returns whether the red and green channels are on
+    pub fn channels(self) -> (bool, bool) {
+        match self {
+            Bicolor::Off => (false, false),
+            Bicolor::Red => (true, false),
+            Bicolor::Green => (false, true),
+            Bicolor::Yellow => (true, true),
+        }
+    }
+}
+
+/// Error identifying which channel failed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error<R, G, B = Infallible> {
+    Red(R),
+    Green(G),
+    Blue(B),
+}
+
+type ColorError<R, G, B> =
+    Error<<R as OutputSwitch>::Error, <G as OutputSwitch>::Error, <B as OutputSwitch>::Error>;
+
+type MixError<R, G, B> = Error<
+    <R as DimmableOutputSwitch>::Error,
+    <G as DimmableOutputSwitch>::Error,
+    <B as DimmableOutputSwitch>::Error,
+>;
+
+/// A LED with independent red, green and blue channels
+pub struct RgbLed<R, G, B> {
+    red: R,
+    green: G,
+    blue: B,
+}
+
+impl<R, G, B> RgbLed<R, G, B> {
+    /// Constructs a new [RgbLed](RgbLed) from its channels
+    pub fn new(red: R, green: G, blue: B) -> Self {
+        RgbLed { red, green, blue }
+    }
+
+    /// Consumes the [RgbLed](RgbLed) and returns the red, green and blue channels
+    pub fn into_inner(self) -> (R, G, B) {
+        (self.red, self.green, self.blue)
+    }
+}
+
+impl<R: OutputSwitch, G: OutputSwitch, B: OutputSwitch> RgbLed<R, G, B> {
+    /// Turns each channel fully on or off to show `color`
+    pub fn set_color(&mut self, color: Color) -> Result<(), ColorError<R, G, B>> {
+        let (red, green, blue) = color.channels();
+        self.red.set_state(red.into()).map_err(Error::Red)?;
+        self.green.set_state(green.into()).map_err(Error::Green)?;
+        self.blue.set_state(blue.into()).map_err(Error::Blue)
+    }
+}
+
+impl<R, G, B> RgbLed<R, G, B>
+where
+    R: DimmableOutputSwitch,
+    G: DimmableOutputSwitch,
+    B: DimmableOutputSwitch,
+{
+    /// Mixes an arbitrary color by setting the brightness of each channel
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use switch_hal::mock;
+    /// use switch_hal::IntoSwitch;
+    /// use switch_hal::rgb::RgbLed;
+    /// # let (r, g, b) = (mock::Pwm::new(1000), mock::Pwm::new(1000), mock::Pwm::new(1000));
+    /// let mut status = RgbLed::new(
+    ///     r.into_active_high_switch(),
+    ///     g.into_active_high_switch(),
+    ///     b.into_active_high_switch(),
+    /// );
+    /// // orange
+    /// status.set_rgb(255, 165, 0).unwrap();
+    /// ```
+    pub fn set_rgb(&mut self, red: u8, green: u8, blue: u8) -> Result<(), MixError<R, G, B>> {
+        self.red.set_brightness(red).map_err(Error::Red)?;
+        self.green.set_brightness(green).map_err(Error::Green)?;
+        self.blue.set_brightness(blue).map_err(Error::Blue)
+    }
+}
+
+/// A LED with independent red and green channels
+///
+/// # Examples
+///
+/// ```
+/// # use switch_hal::mock;
+/// use switch_hal::IntoSwitch;
+/// use switch_hal::rgb::{Bicolor, BicolorLed};
+/// # let (r, g) = (mock::Pin::new(), mock::Pin::new());
+/// let mut status = BicolorLed::new(r.into_active_high_switch(), g.into_active_high_switch());
+/// status.set_color(Bicolor::Yellow).unwrap();
+/// ```
+pub struct BicolorLed<R, G> {
+    red: R,
+    green: G,
+}
+
+impl<R, G> BicolorLed<R, G> {
+    /// Constructs a new [BicolorLed](BicolorLed) from its channels
+    pub fn new(red: R, green: G) -> Self {
+        BicolorLed { red, green }
+    }
+
+    /// Consumes the [BicolorLed](BicolorLed) and returns the red and green channels
+    pub fn into_inner(self) -> (R, G) {
+        (self.red, self.green)
+    }
+}
+
+impl<R: OutputSwitch, G: OutputSwitch> BicolorLed<R, G> {
+    /// Turns each channel fully on or off to show `color`
+    pub fn set_color(&mut self, color: Bicolor) -> Result<(), Error<R::Error, G::Error>> {
+        let (red, green) = color.channels();
+        self.red.set_state(red.into()).map_err(Error::Red)?;
+        self.green.set_state(green.into()).map_err(Error::Green)
+    }
+}
+
+impl<R: DimmableOutputSwitch, G: DimmableOutputSwitch> BicolorLed<R, G> {
+    /// Mixes an arbitrary color by setting the brightness of each channel
+    pub fn set_mix(&mut self, red: u8, green: u8) -> Result<(), Error<R::Error, G::Error>> {
+        self.red.set_brightness(red).map_err(Error::Red)?;
+        self.green.set_brightness(green).map_err(Error::Green)
+    }
+}
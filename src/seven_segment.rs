@@ -0,0 +1,277 @@
+//! Drives seven segment displays wired directly to [OutputSwitch](crate::OutputSwitch)es.
+//!
+//! Segments are ordered `a` through `g`, followed by the decimal point.
+//! Common cathode displays use [ActiveHigh](crate::ActiveHigh) segments
+//! and common anode displays use [ActiveLow](crate::ActiveLow) segments;
+//! nothing else about the driver changes.
+//!
+//! ```text
+//!  --a--
+//! |     |
+//! f     b
+//! |     |
+//!  --g--
+//! |     |
+//! e     c
+//! |     |
+//!  --d--  .dp
+//! ```
+use core::convert::Infallible;
+
+use crate::bank::{self, SwitchBank};
+use crate::OutputSwitch;
+
+/// Bit for the decimal point in a segment pattern
+pub const DP: u8 = 0b1000_0000;
+
+/// Segment patterns for the hexadecimal digits `0` through `F`
+pub const HEX_DIGITS: [u8; 16] = [
+    0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F, 0x77, 0x7C, 0x39, 0x5E, 0x79, 0x71,
+];
+
+/// Returns the segment pattern for `c`, or `None` if it cannot be displayed.
+///
+/// Supports hexadecimal digits, common letters, `-`, `_` and space.
+/// Letters that only have one legible form are displayed the same in either case.
+///
+/// # Examples
+///
+/// ```
+/// use switch_hal::seven_segment::{glyph, HEX_DIGITS};
+///
+/// assert_eq!(Some(HEX_DIGITS[0xb]), glyph('B'));
+/// assert_eq!(None, glyph('W'));
+/// ```
+pub fn glyph(c: char) -> Option<u8> {
+    let pattern = match c {
+        '0'..='9' => HEX_DIGITS[c as usize - '0' as usize],
+        'a' | 'A' => HEX_DIGITS[0xA],
+        'b' | 'B' => HEX_DIGITS[0xB],
+        'C' => HEX_DIGITS[0xC],
+        'c' => 0x58,
+        'd' | 'D' => HEX_DIGITS[0xD],
+        'e' | 'E' => HEX_DIGITS[0xE],
+        'f' | 'F' => HEX_DIGITS[0xF],
+        'g' | 'G' => 0x3D,
+        'H' => 0x76,
+        'h' => 0x74,
+        'i' | 'I' => 0x04,
+        'j' | 'J' => 0x1E,
+        'l' | 'L' => 0x38,
+        'n' | 'N' => 0x54,
+        'O' => HEX_DIGITS[0],
+        'o' => 0x5C,
+        'p' | 'P' => 0x73,
+        'r' | 'R' => 0x50,
+        's' | 'S' => HEX_DIGITS[5],
+        't' | 'T' => 0x78,
+        'U' => 0x3E,
+        'u' => 0x1C,
+        'y' | 'Y' => 0x6E,
+        '-' => 0x40,
+        '_' => 0x08,
+        ' ' => 0x00,
+        _ => return None,
+    };
+    Some(pattern)
+}
+
+/// Error returned by the seven segment drivers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error<E, D = Infallible> {
+    /// A segment could not be written
+    Segment(bank::Error<E>),
+    /// A digit select could not be written
+    Digit(bank::Error<D>),
+    /// The character has no glyph
+    Unsupported(char),
+}
+
+/// A single seven segment digit
+///
+/// # Examples
+///
+/// ```
+/// # use switch_hal::mock;
+/// use switch_hal::IntoSwitch;
+/// use switch_hal::seven_segment::SevenSegment;
+/// # let pins = [(); 8].map(|_| mock::Pin::new());
+/// // common anode
+/// let mut display = SevenSegment::new(pins.map(|pin| pin.into_active_low_switch()));
+/// display.display('7', true).unwrap();
+/// ```
+pub struct SevenSegment<S> {
+    segments: SwitchBank<[S; 8]>,
+}
+
+impl<S: OutputSwitch> SevenSegment<S> {
+    /// Constructs a new [SevenSegment](SevenSegment) from the `a` through `g` segments and decimal point
+    pub fn new(segments: [S; 8]) -> Self {
+        SevenSegment {
+            segments: SwitchBank::new(segments),
+        }
+    }
+
+    /// Consumes the [SevenSegment](SevenSegment) and returns the underlying segments
+    pub fn into_inner(self) -> [S; 8] {
+        self.segments.into_inner()
+    }
+
+    /// Displays `c`, with or without the decimal point
+    pub fn display(&mut self, c: char, dp: bool) -> Result<(), Error<S::Error>> {
+        let pattern = glyph(c).ok_or(Error::Unsupported(c))?;
+        self.write_pattern(if dp { pattern | DP } else { pattern })
+    }
+
+    /// Displays a raw segment pattern, where bit 0 is segment `a` and bit 7 is the decimal point
+    pub fn write_pattern(&mut self, pattern: u8) -> Result<(), Error<S::Error>> {
+        self.segments
+            .write_bits(u32::from(pattern))
+            .map_err(Error::Segment)
+    }
+
+    /// Turns every segment off
+    pub fn clear(&mut self) -> Result<(), Error<S::Error>> {
+        self.write_pattern(0)
+    }
+}
+
+/// A multiplexed, multi-digit seven segment display.
+///
+/// The segments are shared by every digit and each digit has its own digit select switch.
+/// Only one digit is lit at a time, so [tick](Multiplexed::tick) must be called
+/// frequently enough (typically at least `60 * N` times per second) to avoid flicker.
+///
+/// # Examples
+///
+/// ```
+/// # use switch_hal::mock;
+/// use switch_hal::IntoSwitch;
+/// use switch_hal::seven_segment::Multiplexed;
+/// # let segment_pins = [(); 8].map(|_| mock::Pin::new());
+/// # let digit_pins = [(); 4].map(|_| mock::Pin::new());
+/// let mut display = Multiplexed::new(
+///     segment_pins.map(|pin| pin.into_active_high_switch()),
+///     digit_pins.map(|pin| pin.into_active_low_switch()),
+/// );
+/// display.set_text("12.34").unwrap();
+///
+/// // from a periodic timer
+/// display.tick().unwrap();
+/// ```
+pub struct Multiplexed<S, D, const N: usize> {
+    segments: SwitchBank<[S; 8]>,
+    digits: [D; N],
+    buffer: [u8; N],
+    current: usize,
+}
+
+impl<S: OutputSwitch, D: OutputSwitch, const N: usize> Multiplexed<S, D, N> {
+    /// Constructs a new [Multiplexed](Multiplexed) display.
+    ///
+    /// `digits[0]` is the leftmost digit.
+    ///
+    /// # Panics
+    /// Panics if there are no digits.
+    pub fn new(segments: [S; 8], digits: [D; N]) -> Self {
+        assert!(N > 0, "Multiplexed display needs at least one digit");
+        Multiplexed {
+            segments: SwitchBank::new(segments),
+            digits,
+            buffer: [0; N],
+            current: N - 1,
+        }
+    }
+
+    /// Consumes the [Multiplexed](Multiplexed) display and returns the underlying segments and digit selects
+    pub fn into_inner(self) -> ([S; 8], [D; N]) {
+        (self.segments.into_inner(), self.digits)
+    }
+
+    /// Sets the raw segment pattern shown on digit `index` from the next [tick](Multiplexed::tick)
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than the number of digits.
+    pub fn set_pattern(&mut self, index: usize, pattern: u8) {
+        self.buffer[index] = pattern;
+    }
+
+    /// Sets the character shown on digit `index` from the next [tick](Multiplexed::tick)
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than the number of digits.
+    pub fn set_digit(
+        &mut self,
+        index: usize,
+        c: char,
+        dp: bool,
+    ) -> Result<(), Error<S::Error, D::Error>> {
+        let pattern = glyph(c).ok_or(Error::Unsupported(c))?;
+        self.set_pattern(index, if dp { pattern | DP } else { pattern });
+        Ok(())
+    }
+
+    /// Fills the display from the left with `text`, blanking any remaining digits.
+    ///
+    /// A `.` lights the decimal point of the preceding character and does not take a digit of its own.
+    /// Characters that do not fit are ignored, along with their decimal points.
+    /// The buffer is left unchanged if `text` contains an unsupported character.
+    pub fn set_text(&mut self, text: &str) -> Result<(), Error<S::Error, D::Error>> {
+        let mut buffer = [0; N];
+        let mut len = 0;
+        let mut dropped = false;
+        for c in text.chars() {
+            if c == '.' {
+                if dropped {
+                    continue;
+                }
+                if len > 0 && buffer[len - 1] & DP == 0 {
+                    buffer[len - 1] |= DP;
+                    continue;
+                }
+            }
+            let pattern = if c == '.' {
+                DP
+            } else {
+                glyph(c).ok_or(Error::Unsupported(c))?
+            };
+            dropped = len == N;
+            if !dropped {
+                buffer[len] = pattern;
+                len += 1;
+            }
+        }
+        self.buffer = buffer;
+        Ok(())
+    }
+
+    /// Turns the current digit off and lights the next one.
+    ///
+    /// The digit is deselected before the segments change to prevent ghosting.
+    pub fn tick(&mut self) -> Result<(), Error<S::Error, D::Error>> {
+        let index = self.current;
+        self.digits[index]
+            .off()
+            .map_err(|error| Error::Digit(bank::Error { index, error }))?;
+
+        let next = (self.current + 1) % N;
+        self.segments
+            .write_bits(u32::from(self.buffer[next]))
+            .map_err(Error::Segment)?;
+        self.digits[next]
+            .on()
+            .map_err(|error| Error::Digit(bank::Error { index: next, error }))?;
+        self.current = next;
+        Ok(())
+    }
+
+    /// Turns every digit off
+    pub fn blank(&mut self) -> Result<(), Error<S::Error, D::Error>> {
+        for (index, digit) in self.digits.iter_mut().enumerate() {
+            digit
+                .off()
+                .map_err(|error| Error::Digit(bank::Error { index, error }))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+//! Drivers for shift register chains, for when you run out of GPIO.
+//!
+//! Outputs are driven by [Hc595](Hc595) chains and inputs are read from [Hc165](Hc165) chains.
+//!
+//! Each driver implements one of the [port](crate::port) driver traits,
+//! so individual bits are handed out through a [Port](crate::port::Port)
+//! and wrapped in a [Switch](crate::Switch) like any other pin.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::{IntoSwitch, OutputSwitch};
+//! use switch_hal::port::Port;
+//! use switch_hal::shift_register::Hc595;
+//! # let (data, clock, latch) = (mock::Pin::new(), mock::Pin::new(), mock::Pin::new());
+//! let chain = Port::new(Hc595::new(data, clock, latch, 16));
+//! let mut status = chain.output(0).into_active_high_switch();
+//! let mut fault = chain.output(9).into_active_low_switch();
+//! status.on().unwrap();
+//! fault.off().unwrap();
+//! ```
+use embedded_hal::digital::v2::OutputPin;
+
+use crate::port::{InputPortDriver, OutputPortDriver};
+use crate::{InputSwitch, OutputSwitch};
+
+/// A chain of 74HC595 serial in, parallel out, shift registers, bit banged over three pins.
+///
+/// Output `0` is `QA` of the first register in the chain, the one connected to the data pin,
+/// and output `8` is `QA` of the second register.
+/// The clock and latch pins are expected to idle low.
+pub struct Hc595<D, C, L> {
+    data: D,
+    clock: C,
+    latch: L,
+    bits: u8,
+}
+
+impl<D, C, L, E> Hc595<D, C, L>
+where
+    D: OutputPin<Error = E>,
+    C: OutputPin<Error = E>,
+    L: OutputPin<Error = E>,
+{
+    /// Constructs a new [Hc595](Hc595) driver for a chain of `bits` outputs (8 per register, up to 32)
+    ///
+    /// # Panics
+    /// Panics if `bits` is greater than 32.
+    pub fn new(data: D, clock: C, latch: L, bits: u8) -> Self {
+        assert!(bits <= 32, "Hc595 supports chains of at most 32 bits");
+        Hc595 {
+            data,
+            clock,
+            latch,
+            bits,
+        }
+    }
+
+    /// Consumes the [Hc595](Hc595) driver and returns the data, clock and latch pins
+    pub fn into_inner(self) -> (D, C, L) {
+        (self.data, self.clock, self.latch)
+    }
+}
+
+impl<D, C, L, E> OutputPortDriver for Hc595<D, C, L>
+where
+    D: OutputPin<Error = E>,
+    C: OutputPin<Error = E>,
+    L: OutputPin<Error = E>,
+{
+    type Error = E;
+
+    /// Shifts out every bit, the last output first, then pulses the latch
+    fn write(&mut self, outputs: u32) -> Result<(), Self::Error> {
+        for bit in (0..self.bits).rev() {
+            if outputs & (1 << bit) != 0 {
+                self.data.set_high()?;
+            } else {
+                self.data.set_low()?;
+            }
+            self.clock.set_high()?;
+            self.clock.set_low()?;
+        }
+        self.latch.set_high()?;
+        self.latch.set_low()
+    }
+}
+
+/// A chain of 74HC165 parallel in, serial out, shift registers, clocked through switches.
+///
+/// - `load` is on while the parallel inputs are loaded. `SH/LD` is active low,
+///   so this is usually an [ActiveLow](crate::ActiveLow) switch.
+/// - `clock` is on while the clock is high, usually an [ActiveHigh](crate::ActiveHigh) switch.
+/// - `data` is active when the serial output is high, so `QH` is read through an
+///   [ActiveHigh](crate::ActiveHigh) switch and the inverted `QH'` through an [ActiveLow](crate::ActiveLow) switch.
+///
+/// Input `0` is `A` of the first register in the chain, the one connected to the data pin,
+/// and input `8` is `A` of the second register.
+///
+/// # Examples
+///
+/// ```
+/// # use switch_hal::mock::{self, State};
+/// use switch_hal::{InputSwitch, IntoSwitch};
+/// use switch_hal::port::Port;
+/// use switch_hal::shift_register::Hc165;
+/// # let (load, clock, data) = (mock::Pin::new(), mock::Pin::new(), mock::Pin::with_state(State::High));
+/// let panel = Port::new(Hc165::new(
+///     load.into_active_low_switch(),
+///     clock.into_active_high_switch(),
+///     data.into_active_high_switch(),
+///     2,
+/// ));
+/// let start = panel.input(0).into_active_low_switch();
+/// let stop = panel.input(12).into_active_low_switch();
+///
+/// // shift in the whole chain once, then read any number of buttons
+/// panel.refresh().unwrap();
+/// if stop.is_active().unwrap() {
+///     // ...
+/// }
+/// ```
+pub struct Hc165<L, C, D> {
+    load: L,
+    clock: C,
+    data: D,
+    registers: u8,
+}
+
+impl<L, C, D, E> Hc165<L, C, D>
+where
+    L: OutputSwitch<Error = E>,
+    C: OutputSwitch<Error = E>,
+    D: InputSwitch<Error = E>,
+{
+    /// Constructs a new [Hc165](Hc165) driver for a chain of `registers` registers (up to 4)
+    ///
+    /// # Panics
+    /// Panics if `registers` is greater than 4.
+    pub fn new(load: L, clock: C, data: D, registers: u8) -> Self {
+        assert!(
+            registers <= 4,
+            "Hc165 supports chains of at most 4 registers"
+        );
+        Hc165 {
+            load,
+            clock,
+            data,
+            registers,
+        }
+    }
+
+    /// Consumes the [Hc165](Hc165) driver and returns the load, clock and data switches
+    pub fn into_inner(self) -> (L, C, D) {
+        (self.load, self.clock, self.data)
+    }
+}
+
+impl<L, C, D, E> InputPortDriver for Hc165<L, C, D>
+where
+    L: OutputSwitch<Error = E>,
+    C: OutputSwitch<Error = E>,
+    D: InputSwitch<Error = E>,
+{
+    type Error = E;
+
+    /// Loads the parallel inputs, then shifts in every bit, `H` of the first register first
+    fn read(&mut self) -> Result<u32, Self::Error> {
+        self.load.on()?;
+        self.load.off()?;
+
+        let mut inputs = 0;
+        for register in 0..u32::from(self.registers) {
+            for pin in (0..8).rev() {
+                if self.data.is_active()? {
+                    inputs |= 1 << (register * 8 + pin);
+                }
+                self.clock.on()?;
+                self.clock.off()?;
+            }
+        }
+        Ok(inputs)
+    }
+}
@@ -0,0 +1,230 @@
+//! Host side simulator, for running application logic against virtual switches before hardware arrives.
+//!
+//! A [Simulator](Simulator) hands out switches over [VirtualPin](VirtualPin)s,
+//! which implement the `embedded-hal` digital traits just like HAL pins.
+//! The simulator knows whether each switch is [ActiveHigh](crate::ActiveHigh) or [ActiveLow](crate::ActiveLow),
+//! so it can draw LEDs as lit or dark and drive buttons to the right level when their key is pressed.
+//!
+//! Rendering and key handling are independent of any terminal library.
+//! See `examples/simulator.rs` for a terminal panel built on `crossterm`.
+//!
+//! Only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use switch_hal::sim::Simulator;
+//! use switch_hal::{ActiveLow, InputSwitch, OutputSwitch};
+//!
+//! let mut sim = Simulator::new();
+//! let mut led = sim.led::<ActiveLow>("power");
+//! let button = sim.button::<ActiveLow>("start", 's');
+//!
+//! sim.handle_key('s');
+//! if button.is_active().unwrap() {
+//!     led.on().unwrap();
+//! }
+//!
+//! let mut panel = Vec::new();
+//! sim.render(&mut panel).unwrap();
+//! assert_eq!("[*] power\n(s) start: pressed\n", String::from_utf8(panel).unwrap());
+//! ```
+use core::convert::Infallible;
+use std::boxed::Box;
+use std::io;
+use std::string::String;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::vec::Vec;
+
+use embedded_hal::digital::v2::{toggleable, InputPin, OutputPin, StatefulOutputPin};
+
+use crate::{InputSwitch, OutputSwitch, Switch};
+
+/// Simulated pin, shared between the application and the [Simulator](Simulator).
+///
+/// Clones refer to the same pin, and can be sent to another thread.
+#[derive(Clone, Debug)]
+pub struct VirtualPin {
+    high: Arc<AtomicBool>,
+}
+
+impl VirtualPin {
+    fn new(high: bool) -> Self {
+        VirtualPin {
+            high: Arc::new(AtomicBool::new(high)),
+        }
+    }
+
+    fn level(&self) -> bool {
+        self.high.load(Ordering::SeqCst)
+    }
+
+    fn set_level(&self, high: bool) {
+        self.high.store(high, Ordering::SeqCst);
+    }
+}
+
+impl InputPin for VirtualPin {
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(self.level())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(!self.level())
+    }
+}
+
+impl OutputPin for VirtualPin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_level(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_level(true);
+        Ok(())
+    }
+}
+
+impl StatefulOutputPin for VirtualPin {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.is_high()
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        self.is_low()
+    }
+}
+
+impl toggleable::Default for VirtualPin {}
+
+enum Kind {
+    Led,
+    Button(char),
+}
+
+struct Device {
+    name: String,
+    kind: Kind,
+    pin: VirtualPin,
+    // observes the pin through a switch of the same active level as the one handed out
+    switch: Box<dyn InputSwitch<Error = Infallible>>,
+}
+
+impl Device {
+    fn is_active(&self) -> bool {
+        match self.switch.is_active() {
+            Ok(active) => active,
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// A panel of simulated LEDs and buttons
+#[derive(Default)]
+pub struct Simulator {
+    devices: Vec<Device>,
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an LED wired as `ActiveLevel`, returning the switch that drives it.
+    ///
+    /// The LED starts out dark.
+    pub fn led<ActiveLevel>(&mut self, name: &str) -> Switch<VirtualPin, ActiveLevel>
+    where
+        ActiveLevel: 'static,
+        Switch<VirtualPin, ActiveLevel>:
+            InputSwitch<Error = Infallible> + OutputSwitch<Error = Infallible>,
+    {
+        self.add(name, Kind::Led)
+    }
+
+    /// Adds a button wired as `ActiveLevel`, returning the switch that reads it.
+    ///
+    /// Terminals do not report key releases, so each press of `key` toggles
+    /// the button between pressed and released.
+    /// The button starts out released.
+    pub fn button<ActiveLevel>(&mut self, name: &str, key: char) -> Switch<VirtualPin, ActiveLevel>
+    where
+        ActiveLevel: 'static,
+        Switch<VirtualPin, ActiveLevel>:
+            InputSwitch<Error = Infallible> + OutputSwitch<Error = Infallible>,
+    {
+        self.add(name, Kind::Button(key))
+    }
+
+    /// Presses or releases every button bound to `key`.
+    ///
+    /// Returns false if no button is bound to `key`.
+    pub fn handle_key(&self, key: char) -> bool {
+        let mut handled = false;
+        for device in &self.devices {
+            if let Kind::Button(bound) = device.kind {
+                if bound == key {
+                    device.pin.set_level(!device.pin.level());
+                    handled = true;
+                }
+            }
+        }
+        handled
+    }
+
+    /// Whether the LED or button called `name` is lit or pressed
+    pub fn is_active(&self, name: &str) -> Option<bool> {
+        self.devices
+            .iter()
+            .find(|device| device.name == name)
+            .map(Device::is_active)
+    }
+
+    /// Writes the panel, one device per line, as plain text
+    pub fn render<W: io::Write>(&self, mut out: W) -> io::Result<()> {
+        for device in &self.devices {
+            match device.kind {
+                Kind::Led => {
+                    let lamp = if device.is_active() { '*' } else { ' ' };
+                    writeln!(out, "[{}] {}", lamp, device.name)?;
+                }
+                Kind::Button(key) => {
+                    let state = if device.is_active() {
+                        "pressed"
+                    } else {
+                        "released"
+                    };
+                    writeln!(out, "({}) {}: {}", key, device.name, state)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add<ActiveLevel>(&mut self, name: &str, kind: Kind) -> Switch<VirtualPin, ActiveLevel>
+    where
+        ActiveLevel: 'static,
+        Switch<VirtualPin, ActiveLevel>:
+            InputSwitch<Error = Infallible> + OutputSwitch<Error = Infallible>,
+    {
+        let pin = VirtualPin::new(false);
+        let mut switch = Switch::<_, ActiveLevel>::new(pin.clone());
+        match switch.off() {
+            Ok(()) => {}
+            Err(never) => match never {},
+        }
+        self.devices.push(Device {
+            name: String::from(name),
+            kind,
+            pin: pin.clone(),
+            switch: Box::new(switch),
+        });
+        Switch::new(pin)
+    }
+}
@@ -0,0 +1,151 @@
+//! Reads configuration straps, such as hardware revision or address straps, at boot.
+//!
+//! Straps are sampled several times and must read the same value every time,
+//! which catches floating or poorly pulled lines.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock::{self, State};
+//! use core::convert::TryFrom;
+//! use switch_hal::IntoSwitch;
+//! use switch_hal::strap::StrapReader;
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Revision {
+//!     A,
+//!     B,
+//! }
+//!
+//! impl TryFrom<u8> for Revision {
+//!     type Error = u8;
+//!
+//!     fn try_from(value: u8) -> Result<Self, Self::Error> {
+//!         match value {
+//!             0b00 => Ok(Revision::A),
+//!             0b01 => Ok(Revision::B),
+//!             other => Err(other),
+//!         }
+//!     }
+//! }
+//!
+//! # let pins = [State::High, State::Low].map(mock::Pin::with_state);
+//! let straps = StrapReader::new(pins.map(|pin| pin.into_active_high_switch()));
+//! assert_eq!(Revision::B, straps.decode::<Revision>().unwrap());
+//! ```
+use core::convert::{Infallible, TryFrom};
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::bank::{self, SwitchBank};
+use crate::InputSwitch;
+
+/// Number of samples taken by [StrapReader::new](StrapReader::new)
+pub const DEFAULT_SAMPLES: usize = 3;
+
+/// Error returned by a [StrapReader](StrapReader)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error<E, D = Infallible> {
+    /// A strap could not be read
+    Switch(bank::Error<E>),
+    /// Two samples disagreed
+    Inconsistent { first: u8, other: u8 },
+    /// The value could not be decoded
+    Decode(D),
+}
+
+impl<E, D> From<bank::Error<E>> for Error<E, D> {
+    fn from(error: bank::Error<E>) -> Self {
+        Error::Switch(error)
+    }
+}
+
+/// Samples a group of [InputSwitch](crate::InputSwitch)es and decodes them into a value.
+///
+/// `switches[0]` is the least significant bit and an active switch reads as a set bit.
+/// Up to 8 straps are supported.
+pub struct StrapReader<B> {
+    bank: SwitchBank<B>,
+    samples: usize,
+}
+
+impl<S: InputSwitch, const N: usize> StrapReader<[S; N]> {
+    /// Constructs a new [StrapReader](StrapReader) that takes [DEFAULT_SAMPLES](DEFAULT_SAMPLES) samples
+    ///
+    /// # Panics
+    /// Panics if there are more than 8 straps.
+    pub fn new(switches: [S; N]) -> Self {
+        Self::with_samples(switches, DEFAULT_SAMPLES)
+    }
+
+    /// Constructs a new [StrapReader](StrapReader) that takes `samples` samples.
+    ///
+    /// At least one sample is always taken.
+    ///
+    /// # Panics
+    /// Panics if there are more than 8 straps.
+    pub fn with_samples(switches: [S; N], samples: usize) -> Self {
+        assert!(N <= 8, "StrapReader supports at most 8 straps");
+        StrapReader {
+            bank: SwitchBank::new(switches),
+            samples: samples.max(1),
+        }
+    }
+
+    /// Consumes the [StrapReader](StrapReader) and returns the underlying switches
+    pub fn into_inner(self) -> [S; N] {
+        self.bank.into_inner()
+    }
+
+    /// Samples the straps back to back, returning the value if every sample agrees
+    pub fn read(&self) -> Result<u8, Error<S::Error>> {
+        let first = self.sample()?;
+        for _ in 1..self.samples {
+            self.check(first)?;
+        }
+        Ok(first)
+    }
+
+    /// Samples the straps, waiting `interval_us` microseconds between samples,
+    /// returning the value if every sample agrees
+    pub fn read_with_delay<D: DelayUs<u32>>(
+        &self,
+        delay: &mut D,
+        interval_us: u32,
+    ) -> Result<u8, Error<S::Error>> {
+        let first = self.sample()?;
+        for _ in 1..self.samples {
+            delay.delay_us(interval_us);
+            self.check(first)?;
+        }
+        Ok(first)
+    }
+
+    /// Samples the straps and decodes the value into `T`
+    pub fn decode<T: TryFrom<u8>>(&self) -> Result<T, Error<S::Error, T::Error>> {
+        let value = self.read().map_err(widen)?;
+        T::try_from(value).map_err(Error::Decode)
+    }
+
+    fn sample(&self) -> Result<u8, Error<S::Error>> {
+        // Only the low 8 straps are used
+        Ok(self.bank.read_bits()? as u8)
+    }
+
+    fn check(&self, first: u8) -> Result<(), Error<S::Error>> {
+        let other = self.sample()?;
+        if other == first {
+            Ok(())
+        } else {
+            Err(Error::Inconsistent { first, other })
+        }
+    }
+}
+
+fn widen<E, D>(error: Error<E>) -> Error<E, D> {
+    match error {
+        Error::Switch(e) => Error::Switch(e),
+        Error::Inconsistent { first, other } => Error::Inconsistent { first, other },
+        Error::Decode(never) => match never {},
+    }
+}
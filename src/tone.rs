@@ -0,0 +1,293 @@
+//! Tones and melodies on a buzzer, such as a piezo driven through a switching transistor.
+//!
+//! [Tone](Tone) bit bangs a square wave on any [ToggleableOutputSwitch](crate::ToggleableOutputSwitch)
+//! from a periodic timer tick.
+//! Switches that can't be toggled by the hal can be wrapped in [SoftToggle](SoftToggle).
+//! Hardware PWM timers can be used instead by implementing [ToneGenerator](ToneGenerator).
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! use switch_hal::IntoSwitch;
+//! use switch_hal::tone::{pitch, Melody, Note, Tone};
+//!
+//! static JINGLE: [Note; 3] = [
+//!     Note::new(pitch::C5, 100),
+//!     Note::rest(50),
+//!     Note::new(pitch::G4, 200),
+//! ];
+//!
+//! # let pin = mock::Pin::with_state(mock::State::Low);
+//! let buzzer = Tone::new(pin.into_active_high_switch(), 8_000);
+//! let mut melody = Melody::new(buzzer, &JINGLE, 8_000);
+//!
+//! // from a timer interrupt at 8 kHz
+//! while !melody.is_finished() {
+//!     melody.tick().unwrap();
+//! }
+//! ```
+use crate::{OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch};
+
+/// Frequencies of common notes, in hertz
+pub mod pitch {
+    pub const C4: u32 = 262;
+    pub const D4: u32 = 294;
+    pub const E4: u32 = 330;
+    pub const F4: u32 = 349;
+    pub const G4: u32 = 392;
+    pub const A4: u32 = 440;
+    pub const B4: u32 = 494;
+    pub const C5: u32 = 523;
+    pub const D5: u32 = 587;
+    pub const E5: u32 = 659;
+    pub const F5: u32 = 698;
+    pub const G5: u32 = 784;
+    pub const A5: u32 = 880;
+    pub const B5: u32 = 988;
+    pub const C6: u32 = 1047;
+}
+
+/// Something that can play a tone, such as a bit banged switch or a hardware PWM timer
+pub trait ToneGenerator {
+    type Error;
+
+    /// Starts playing a tone at `frequency_hz`, replacing any tone already playing
+    fn start(&mut self, frequency_hz: u32) -> Result<(), Self::Error>;
+
+    /// Silences the output
+    fn stop(&mut self) -> Result<(), Self::Error>;
+
+    /// Called on every timer tick.
+    ///
+    /// Generators that run without software intervention, such as hardware PWM timers,
+    /// can rely on the default implementation, which does nothing.
+    fn tick(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Generates a square wave by toggling a switch from a periodic tick.
+///
+/// The highest frequency that can be generated is half the tick rate.
+pub struct Tone<S> {
+    switch: S,
+    tick_hz: u32,
+    half_period: u32,
+    elapsed: u32,
+}
+
+impl<S, E> Tone<S>
+where
+    S: OutputSwitch<Error = E> + ToggleableOutputSwitch<Error = E>,
+{
+    /// Constructs a new, silent, [Tone](Tone) generator that will be ticked `tick_hz` times per second
+    pub fn new(switch: S, tick_hz: u32) -> Self {
+        Tone {
+            switch,
+            tick_hz,
+            half_period: 0,
+            elapsed: 0,
+        }
+    }
+
+    /// Consumes the [Tone](Tone) generator and returns the underlying switch
+    pub fn into_inner(self) -> S {
+        self.switch
+    }
+
+    /// Returns true if a tone is playing
+    pub fn is_playing(&self) -> bool {
+        self.half_period != 0
+    }
+}
+
+impl<S, E> ToneGenerator for Tone<S>
+where
+    S: OutputSwitch<Error = E> + ToggleableOutputSwitch<Error = E>,
+{
+    type Error = E;
+
+    /// Starts a tone at `frequency_hz`, rounded to a whole number of ticks.
+    /// A frequency of `0` stops the tone.
+    fn start(&mut self, frequency_hz: u32) -> Result<(), Self::Error> {
+        if frequency_hz == 0 {
+            return self.stop();
+        }
+        self.half_period = (self.tick_hz / frequency_hz.saturating_mul(2)).max(1);
+        self.elapsed = 0;
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Self::Error> {
+        self.half_period = 0;
+        self.switch.off()
+    }
+
+    fn tick(&mut self) -> Result<(), Self::Error> {
+        if self.half_period == 0 {
+            return Ok(());
+        }
+        self.elapsed += 1;
+        if self.elapsed >= self.half_period {
+            self.elapsed = 0;
+            self.switch.toggle()?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds [ToggleableOutputSwitch](crate::ToggleableOutputSwitch) to any [OutputSwitch](crate::OutputSwitch)
+/// by remembering the last state it was set to.
+///
+/// The switch is assumed to be off until it is first written.
+pub struct SoftToggle<S> {
+    switch: S,
+    on: bool,
+}
+
+impl<S: OutputSwitch> SoftToggle<S> {
+    /// Wraps `switch`, which is assumed to be off
+    pub fn new(switch: S) -> Self {
+        SoftToggle { switch, on: false }
+    }
+
+    /// Consumes the [SoftToggle](SoftToggle) and returns the wrapped switch
+    pub fn into_inner(self) -> S {
+        self.switch
+    }
+}
+
+impl<S: OutputSwitch> OutputSwitch for SoftToggle<S> {
+    type Error = S::Error;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        self.switch.on()?;
+        self.on = true;
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.switch.off()?;
+        self.on = false;
+        Ok(())
+    }
+}
+
+impl<S: OutputSwitch> ToggleableOutputSwitch for SoftToggle<S> {
+    type Error = S::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        if self.on {
+            self.off()
+        } else {
+            self.on()
+        }
+    }
+}
+
+impl<S: OutputSwitch> StatefulOutputSwitch for SoftToggle<S> {
+    type Error = S::Error;
+
+    fn is_on(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.on)
+    }
+
+    fn is_off(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.on)
+    }
+}
+
+/// A note, or rest, in a [Melody](Melody)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Note {
+    /// Frequency in hertz, `0` for a rest
+    pub frequency_hz: u32,
+    /// Duration in milliseconds
+    pub duration_ms: u32,
+}
+
+impl Note {
+    /// A note at `frequency_hz` lasting `duration_ms` milliseconds
+    pub const fn new(frequency_hz: u32, duration_ms: u32) -> Self {
+        Note {
+            frequency_hz,
+            duration_ms,
+        }
+    }
+
+    /// Silence lasting `duration_ms` milliseconds
+    pub const fn rest(duration_ms: u32) -> Self {
+        Note::new(0, duration_ms)
+    }
+}
+
+/// Plays a table of [Note](Note)s on a [ToneGenerator](ToneGenerator), advanced by a periodic tick.
+pub struct Melody<'a, G> {
+    generator: G,
+    notes: &'a [Note],
+    tick_hz: u32,
+    next: usize,
+    remaining: u32,
+    finished: bool,
+}
+
+impl<'a, G: ToneGenerator> Melody<'a, G> {
+    /// Constructs a new [Melody](Melody) player that will be ticked `tick_hz` times per second.
+    ///
+    /// The first note starts on the first [tick](Melody::tick).
+    pub fn new(generator: G, notes: &'a [Note], tick_hz: u32) -> Self {
+        Melody {
+            generator,
+            notes,
+            tick_hz,
+            next: 0,
+            remaining: 0,
+            finished: false,
+        }
+    }
+
+    /// Consumes the [Melody](Melody) player and returns the underlying generator
+    pub fn into_inner(self) -> G {
+        self.generator
+    }
+
+    /// Returns true once every note has played
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Starts the melody again from the first note
+    pub fn restart(&mut self) {
+        self.next = 0;
+        self.remaining = 0;
+        self.finished = false;
+    }
+
+    /// Advances the melody, and the generator, by one tick.
+    ///
+    /// The generator is stopped once the last note has played.
+    pub fn tick(&mut self) -> Result<(), G::Error> {
+        if self.finished {
+            return Ok(());
+        }
+        while self.remaining == 0 {
+            match self.notes.get(self.next) {
+                Some(note) => {
+                    self.next += 1;
+                    let ticks = u64::from(note.duration_ms) * u64::from(self.tick_hz) / 1000;
+                    self.remaining = ticks as u32;
+                    if self.remaining > 0 {
+                        self.generator.start(note.frequency_hz)?;
+                    }
+                }
+                None => {
+                    self.finished = true;
+                    return self.generator.stop();
+                }
+            }
+        }
+        self.remaining -= 1;
+        self.generator.tick()
+    }
+}
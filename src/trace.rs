@@ -0,0 +1,230 @@
+//! Records switch state changes and exports them as a Value Change Dump, viewable in GTKWave.
+//!
+//! A [Tracer](Tracer) timestamps changes with a [Clock](crate::clock::Clock).
+//! Switches are wrapped with [Tracer::trace](Tracer::trace), which names the signal,
+//! and the wrapper is used in place of the switch.
+//! Signals record the logical state, so `1` means on or active regardless of the `ActiveLevel`.
+//!
+//! Only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use switch_hal::mock;
+//! # let led_pin = mock::Pin::with_state(mock::State::High);
+//! # let button_pin = mock::Pin::with_state(mock::State::Low);
+//! use switch_hal::mock::MockClock;
+//! use switch_hal::trace::Tracer;
+//! use switch_hal::{InputSwitch, IntoSwitch, OutputSwitch};
+//!
+//! let clock = MockClock::new();
+//! let tracer = Tracer::new(&clock);
+//! let mut led = tracer.trace("led", led_pin.into_active_low_switch());
+//! let button = tracer.trace("button", button_pin.into_active_low_switch());
+//!
+//! if button.is_active().unwrap() {
+//!     led.on().unwrap();
+//! }
+//! clock.advance(5);
+//! led.off().unwrap();
+//!
+//! let vcd = tracer.to_vcd();
+//! assert!(vcd.contains("$var wire 1 ! led $end"));
+//! assert!(vcd.contains("#5\n0!\n"));
+//! ```
+use core::cell::RefCell;
+use std::io;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::clock::Clock;
+use crate::{InputSwitch, OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch};
+
+struct Change {
+    time: u64,
+    signal: usize,
+    active: Option<bool>,
+}
+
+struct Recording {
+    names: Vec<String>,
+    last: Vec<Option<bool>>,
+    changes: Vec<Change>,
+}
+
+/// Collects state changes of traced switches, timestamped in milliseconds since the tracer was created
+pub struct Tracer<'c, C: Clock> {
+    clock: &'c C,
+    start: C::Instant,
+    recording: RefCell<Recording>,
+}
+
+impl<'c, C: Clock> Tracer<'c, C> {
+    /// Constructs a new [Tracer](Tracer), starting at the current time of `clock`
+    pub fn new(clock: &'c C) -> Self {
+        Tracer {
+            clock,
+            start: clock.now(),
+            recording: RefCell::new(Recording {
+                names: Vec::new(),
+                last: Vec::new(),
+                changes: Vec::new(),
+            }),
+        }
+    }
+
+    /// Wraps `switch` so its state changes are recorded as the signal `name`.
+    ///
+    /// The state is unknown until the switch is first read or written.
+    pub fn trace<S>(&self, name: &str, switch: S) -> Traced<'_, 'c, S, C> {
+        let mut recording = self.recording.borrow_mut();
+        recording.names.push(name.replace(char::is_whitespace, "_"));
+        recording.last.push(None);
+        Traced {
+            switch,
+            tracer: self,
+            signal: recording.names.len() - 1,
+        }
+    }
+
+    /// Writes every recorded change as a Value Change Dump
+    pub fn write_vcd<W: io::Write>(&self, mut out: W) -> io::Result<()> {
+        let recording = self.recording.borrow();
+        writeln!(out, "$timescale 1ms $end")?;
+        writeln!(out, "$scope module switches $end")?;
+        for (signal, name) in recording.names.iter().enumerate() {
+            writeln!(out, "$var wire 1 {} {} $end", identifier(signal), name)?;
+        }
+        writeln!(out, "$upscope $end")?;
+        writeln!(out, "$enddefinitions $end")?;
+
+        writeln!(out, "$dumpvars")?;
+        for signal in 0..recording.names.len() {
+            writeln!(out, "x{}", identifier(signal))?;
+        }
+        writeln!(out, "$end")?;
+
+        let mut time = None;
+        for change in &recording.changes {
+            if time != Some(change.time) {
+                writeln!(out, "#{}", change.time)?;
+                time = Some(change.time);
+            }
+            let value = match change.active {
+                Some(true) => '1',
+                Some(false) => '0',
+                None => 'x',
+            };
+            writeln!(out, "{}{}", value, identifier(change.signal))?;
+        }
+        Ok(())
+    }
+
+    /// Returns every recorded change as a Value Change Dump
+    pub fn to_vcd(&self) -> String {
+        let mut vcd = Vec::new();
+        self.write_vcd(&mut vcd)
+            .expect("writing to a Vec never fails");
+        String::from_utf8(vcd).expect("VCD is ASCII, unless a signal name is not")
+    }
+
+    fn record(&self, signal: usize, active: Option<bool>) {
+        let mut recording = self.recording.borrow_mut();
+        if recording.last[signal] == active {
+            return;
+        }
+        recording.last[signal] = active;
+        let time = self.clock.millis_since(self.start);
+        recording.changes.push(Change {
+            time,
+            signal,
+            active,
+        });
+    }
+
+    fn last(&self, signal: usize) -> Option<bool> {
+        self.recording.borrow().last[signal]
+    }
+}
+
+/// VCD identifier codes are strings of printable ASCII characters, `!` through `~`
+fn identifier(mut signal: usize) -> String {
+    const FIRST: u8 = b'!';
+    const COUNT: usize = (b'~' - b'!' + 1) as usize;
+
+    let mut code = String::new();
+    loop {
+        code.push(char::from(FIRST + (signal % COUNT) as u8));
+        signal /= COUNT;
+        if signal == 0 {
+            return code;
+        }
+        signal -= 1;
+    }
+}
+
+/// A switch whose state changes are recorded by a [Tracer](Tracer)
+pub struct Traced<'t, 'c, S, C: Clock> {
+    switch: S,
+    tracer: &'t Tracer<'c, C>,
+    signal: usize,
+}
+
+impl<'t, 'c, S, C: Clock> Traced<'t, 'c, S, C> {
+    /// Consumes the [Traced](Traced) switch and returns the wrapped switch
+    pub fn into_inner(self) -> S {
+        self.switch
+    }
+}
+
+impl<'t, 'c, S: InputSwitch, C: Clock> InputSwitch for Traced<'t, 'c, S, C> {
+    type Error = S::Error;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        let active = self.switch.is_active()?;
+        self.tracer.record(self.signal, Some(active));
+        Ok(active)
+    }
+}
+
+impl<'t, 'c, S: OutputSwitch, C: Clock> OutputSwitch for Traced<'t, 'c, S, C> {
+    type Error = S::Error;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        self.switch.on()?;
+        self.tracer.record(self.signal, Some(true));
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.switch.off()?;
+        self.tracer.record(self.signal, Some(false));
+        Ok(())
+    }
+}
+
+/// Records the toggled state if the previous state is known, otherwise records an unknown value
+impl<'t, 'c, S: ToggleableOutputSwitch, C: Clock> ToggleableOutputSwitch for Traced<'t, 'c, S, C> {
+    type Error = S::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.switch.toggle()?;
+        let toggled = self.tracer.last(self.signal).map(|active| !active);
+        self.tracer.record(self.signal, toggled);
+        Ok(())
+    }
+}
+
+impl<'t, 'c, S: StatefulOutputSwitch, C: Clock> StatefulOutputSwitch for Traced<'t, 'c, S, C> {
+    type Error = S::Error;
+
+    fn is_on(&mut self) -> Result<bool, Self::Error> {
+        let on = self.switch.is_on()?;
+        self.tracer.record(self.signal, Some(on));
+        Ok(on)
+    }
+
+    fn is_off(&mut self) -> Result<bool, Self::Error> {
+        self.is_on().map(|on| !on)
+    }
+}
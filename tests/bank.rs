@@ -0,0 +1,131 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use embedded_hal::digital::v2::InputPin;
+use switch_hal::bank::{Error, SwitchBank};
+use switch_hal::mock::{MockError, Pin, State};
+use switch_hal::{IntoSwitch, SwitchState};
+
+mod write_bits {
+    use super::*;
+    use switch_hal::OutputSwitch;
+
+    #[test]
+    fn active_high_switches_follow_bits() {
+        let mut leds = SwitchBank::new([(); 4].map(|_| Pin::new().into_active_high_switch()));
+        leds.write_bits(0b0101).unwrap();
+
+        let pins = leds.into_inner().map(|led| led.into_pin());
+        assert_eq!(true, pins[0].is_high().unwrap());
+        assert_eq!(false, pins[1].is_high().unwrap());
+        assert_eq!(true, pins[2].is_high().unwrap());
+        assert_eq!(false, pins[3].is_high().unwrap());
+    }
+
+    #[test]
+    fn active_low_switches_are_inverted() {
+        let mut leds = SwitchBank::new([(); 2].map(|_| Pin::new().into_active_low_switch()));
+        leds.write_bits(0b01).unwrap();
+
+        let pins = leds.into_inner().map(|led| led.into_pin());
+        assert_eq!(true, pins[0].is_low().unwrap());
+        assert_eq!(true, pins[1].is_high().unwrap());
+    }
+
+    #[test]
+    fn bits_beyond_bank_are_ignored() {
+        let mut leds = SwitchBank::new([(); 2].map(|_| Pin::new().into_active_high_switch()));
+        leds.write_bits(0b1111_1110).unwrap();
+
+        let pins = leds.into_inner().map(|led| led.into_pin());
+        assert_eq!(true, pins[0].is_low().unwrap());
+        assert_eq!(true, pins[1].is_high().unwrap());
+    }
+
+    #[test]
+    fn mixed_active_levels() {
+        let mut green = Pin::new().into_active_high_switch();
+        let mut red = Pin::new().into_active_low_switch();
+
+        let mut leds = SwitchBank::new([&mut green as &mut dyn OutputSwitch<Error = _>, &mut red]);
+        leds.write_bits(0b11).unwrap();
+
+        assert_eq!(true, green.into_pin().is_high().unwrap());
+        assert_eq!(true, red.into_pin().is_low().unwrap());
+    }
+
+    #[test]
+    fn set_states() {
+        let mut leds = SwitchBank::new([(); 2].map(|_| Pin::new().into_active_high_switch()));
+        leds.set_states([SwitchState::Off, SwitchState::On])
+            .unwrap();
+
+        let pins = leds.into_inner().map(|led| led.into_pin());
+        assert_eq!(true, pins[0].is_low().unwrap());
+        assert_eq!(true, pins[1].is_high().unwrap());
+    }
+}
+
+mod read_bits {
+    use super::*;
+    use switch_hal::InputSwitch;
+
+    #[test]
+    fn active_switches_set_bits() {
+        let states = [State::High, State::Low, State::High, State::High];
+        let dip = SwitchBank::new(states.map(|s| Pin::with_state(s).into_active_high_switch()));
+        assert_eq!(0b1101, dip.read_bits().unwrap());
+    }
+
+    #[test]
+    fn active_low_switches_are_inverted() {
+        let states = [State::High, State::Low, State::High, State::High];
+        let dip = SwitchBank::new(states.map(|s| Pin::with_state(s).into_active_low_switch()));
+        assert_eq!(0b0010, dip.read_bits().unwrap());
+    }
+
+    #[test]
+    fn mixed_active_levels() {
+        let first = Pin::with_state(State::Low).into_active_low_switch();
+        let second = Pin::with_state(State::High).into_active_high_switch();
+
+        let dip = SwitchBank::new([&first as &dyn InputSwitch<Error = _>, &second]);
+        assert_eq!(0b11, dip.read_bits().unwrap());
+    }
+
+    #[test]
+    fn reports_failing_switch() {
+        let dip = SwitchBank::new([
+            Pin::with_state(State::High).into_active_high_switch(),
+            Pin::with_state(State::High).into_active_high_switch(),
+            Pin::new().into_active_high_switch(),
+        ]);
+        assert_eq!(
+            Error {
+                index: 2,
+                error: MockError::Uninitialized
+            },
+            dip.read_bits().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn states() {
+        let states = [State::Low, State::High];
+        let dip = SwitchBank::new(states.map(|s| Pin::with_state(s).into_active_high_switch()));
+
+        let mut states = dip.states();
+        assert_eq!(Some(Ok(SwitchState::Off)), states.next());
+        assert_eq!(Some(Ok(SwitchState::On)), states.next());
+        assert_eq!(None, states.next());
+    }
+}
+
+mod size {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "at most 32 switches")]
+    fn more_than_32_switches_panics() {
+        SwitchBank::new([(); 40].map(|_| Pin::new().into_active_high_switch()));
+    }
+}
@@ -0,0 +1,204 @@
+use switch_hal::bar_graph::{BarGraph, Mode};
+use switch_hal::{bank, OutputSwitch};
+
+/// Records its state and how many times it was written
+#[derive(Default)]
+struct Segment {
+    on: bool,
+    writes: usize,
+    fail: bool,
+}
+
+impl OutputSwitch for Segment {
+    type Error = &'static str;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        if self.fail {
+            return Err("write failed");
+        }
+        self.on = true;
+        self.writes += 1;
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        if self.fail {
+            return Err("write failed");
+        }
+        self.on = false;
+        self.writes += 1;
+        Ok(())
+    }
+}
+
+fn graph() -> BarGraph<Segment, 5> {
+    BarGraph::new(Default::default(), 0, 100)
+}
+
+fn lit(graph: BarGraph<Segment, 5>) -> [bool; 5] {
+    graph.into_inner().map(|segment| segment.on)
+}
+
+mod bar {
+    use super::*;
+
+    #[test]
+    fn min_is_all_off() {
+        let mut graph = graph();
+        graph.show(0).unwrap();
+        assert_eq!([false; 5], lit(graph));
+    }
+
+    #[test]
+    fn max_is_all_on() {
+        let mut graph = graph();
+        graph.show(100).unwrap();
+        assert_eq!([true; 5], lit(graph));
+    }
+
+    #[test]
+    fn lights_proportional_segments() {
+        let mut graph = graph();
+        graph.show(60).unwrap();
+        assert_eq!([true, true, true, false, false], lit(graph));
+    }
+
+    #[test]
+    fn clamps_below_range() {
+        let mut graph = graph();
+        graph.show(-20).unwrap();
+        assert_eq!([false; 5], lit(graph));
+    }
+
+    #[test]
+    fn negative_ranges() {
+        let mut graph = BarGraph::<Segment, 4>::new(Default::default(), -40, 40);
+        graph.show(0).unwrap();
+        let lit = graph.into_inner().map(|segment| segment.on);
+        assert_eq!([true, true, false, false], lit);
+    }
+}
+
+mod range {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "range must not be empty")]
+    fn empty_range_panics() {
+        BarGraph::<Segment, 2>::new(Default::default(), 5, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "range must not be empty")]
+    fn inverted_range_panics() {
+        BarGraph::<Segment, 2>::new(Default::default(), 10, 0);
+    }
+}
+
+mod dot {
+    use super::*;
+
+    #[test]
+    fn lights_only_top_segment() {
+        let mut graph = graph();
+        graph.set_mode(Mode::Dot);
+        graph.show(60).unwrap();
+        assert_eq!([false, false, true, false, false], lit(graph));
+    }
+
+    #[test]
+    fn min_is_all_off() {
+        let mut graph = graph();
+        graph.set_mode(Mode::Dot);
+        graph.show(0).unwrap();
+        assert_eq!([false; 5], lit(graph));
+    }
+}
+
+mod writes {
+    use super::*;
+
+    #[test]
+    fn first_show_writes_every_segment() {
+        let mut graph = graph();
+        graph.show(40).unwrap();
+
+        let writes = graph.into_inner().map(|segment| segment.writes);
+        assert_eq!([1; 5], writes);
+    }
+
+    #[test]
+    fn only_changed_segments_are_written() {
+        let mut graph = graph();
+        graph.show(40).unwrap();
+        graph.show(60).unwrap();
+
+        let writes = graph.into_inner().map(|segment| segment.writes);
+        assert_eq!([1, 1, 2, 1, 1], writes);
+    }
+
+    #[test]
+    fn reports_failing_segment() {
+        let mut segments: [Segment; 5] = Default::default();
+        segments[3].fail = true;
+        let mut graph = BarGraph::new(segments, 0, 100);
+
+        assert_eq!(
+            bank::Error {
+                index: 3,
+                error: "write failed"
+            },
+            graph.show(100).unwrap_err()
+        );
+    }
+}
+
+mod blink {
+    use super::*;
+
+    #[test]
+    fn top_segment_blinks_when_over_range() {
+        let mut graph = graph();
+        graph.set_blink_over_range(true);
+        graph.show(120).unwrap();
+        graph.tick().unwrap();
+        assert_eq!([true, true, true, true, false], lit(graph));
+    }
+
+    #[test]
+    fn top_segment_blinks_back_on() {
+        let mut graph = graph();
+        graph.set_blink_over_range(true);
+        graph.show(120).unwrap();
+        graph.tick().unwrap();
+        graph.tick().unwrap();
+        assert_eq!([true; 5], lit(graph));
+    }
+
+    #[test]
+    fn does_not_blink_at_max() {
+        let mut graph = graph();
+        graph.set_blink_over_range(true);
+        graph.show(100).unwrap();
+        graph.tick().unwrap();
+        assert_eq!([true; 5], lit(graph));
+    }
+
+    #[test]
+    fn does_not_blink_when_disabled() {
+        let mut graph = graph();
+        graph.show(120).unwrap();
+        graph.tick().unwrap();
+        assert_eq!([true; 5], lit(graph));
+    }
+
+    #[test]
+    fn returning_to_range_stops_blinking() {
+        let mut graph = graph();
+        graph.set_blink_over_range(true);
+        graph.show(120).unwrap();
+        graph.tick().unwrap();
+        graph.show(100).unwrap();
+        assert_eq!([true; 5], lit(graph));
+    }
+}
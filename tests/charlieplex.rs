@@ -0,0 +1,290 @@
+mod common;
+
+use common::take;
+use embedded_hal::digital::v2::{InputPin, IoPin, OutputPin, PinState};
+use switch_hal::bank;
+use switch_hal::charlieplex::{pins_for, Charlieplex, Error};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Mode {
+    Floating,
+    Low,
+    High,
+}
+
+type Log = common::Log<(usize, Mode)>;
+
+/// Records every mode change to a shared log
+struct Input {
+    index: usize,
+    log: Log,
+    fail: bool,
+}
+
+struct Output {
+    index: usize,
+    log: Log,
+    fail: bool,
+}
+
+impl InputPin for Input {
+    type Error = &'static str;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+impl OutputPin for Output {
+    type Error = &'static str;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn into_output(
+    index: usize,
+    log: Log,
+    fail: bool,
+    state: PinState,
+) -> Result<Output, &'static str> {
+    if fail {
+        return Err("mode change failed");
+    }
+    let mode = match state {
+        PinState::Low => Mode::Low,
+        PinState::High => Mode::High,
+    };
+    log.borrow_mut().push((index, mode));
+    Ok(Output { index, log, fail })
+}
+
+impl IoPin<Input, Output> for Input {
+    type Error = &'static str;
+
+    fn into_input_pin(self) -> Result<Input, Self::Error> {
+        Ok(self)
+    }
+
+    fn into_output_pin(self, state: PinState) -> Result<Output, Self::Error> {
+        into_output(self.index, self.log, self.fail, state)
+    }
+}
+
+impl IoPin<Input, Output> for Output {
+    type Error = &'static str;
+
+    fn into_input_pin(self) -> Result<Input, Self::Error> {
+        self.log.borrow_mut().push((self.index, Mode::Floating));
+        Ok(Input {
+            index: self.index,
+            log: self.log,
+            fail: self.fail,
+        })
+    }
+
+    fn into_output_pin(self, state: PinState) -> Result<Output, Self::Error> {
+        into_output(self.index, self.log, self.fail, state)
+    }
+}
+
+fn leds() -> (Charlieplex<Input, Output, 3>, Log) {
+    let log = Log::default();
+    let pins = [0, 1, 2].map(|index| Input {
+        index,
+        log: log.clone(),
+        fail: false,
+    });
+    (Charlieplex::new(pins), log)
+}
+
+fn unlogged<const N: usize>() -> [Input; N] {
+    [(); N].map(|_| Input {
+        index: 0,
+        log: Log::default(),
+        fail: false,
+    })
+}
+
+mod mapping {
+    use super::*;
+
+    #[test]
+    fn every_pair_is_mapped_once() {
+        let pairs: Vec<_> = (0..12).map(|index| pins_for(index, 4).unwrap()).collect();
+        for anode in 0..4 {
+            for cathode in 0..4 {
+                let expected = if anode == cathode { 0 } else { 1 };
+                let count = pairs
+                    .iter()
+                    .filter(|&&pair| pair == (anode, cathode))
+                    .count();
+                assert_eq!(expected, count);
+            }
+        }
+    }
+
+    #[test]
+    fn too_few_pins() {
+        assert_eq!(None, pins_for(0, 1));
+    }
+
+    #[test]
+    fn len() {
+        let (leds, _) = leds();
+        assert_eq!(6, leds.len());
+        assert!(!leds.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "from 2 to 32 pins")]
+    fn single_pin_panics() {
+        Charlieplex::new(unlogged::<1>());
+    }
+
+    #[test]
+    #[should_panic(expected = "from 2 to 32 pins")]
+    fn more_than_32_pins_panics() {
+        Charlieplex::new(unlogged::<33>());
+    }
+
+    #[test]
+    fn set_and_clear() {
+        let (mut leds, _) = leds();
+        leds.set(3, true);
+        assert!(leds.is_set(3));
+        assert!(!leds.is_set(2));
+
+        leds.clear();
+        assert!(!leds.is_set(3));
+    }
+}
+
+mod scanning {
+    use super::*;
+
+    #[test]
+    fn drives_lit_led() {
+        let (mut leds, log) = leds();
+        // anode on pin 0, cathode on pin 2
+        leds.set(1, true);
+        leds.tick().unwrap();
+
+        assert_eq!(vec![(2, Mode::Low), (0, Mode::High)], take(&log));
+    }
+
+    #[test]
+    fn drives_every_lit_cathode_for_anode() {
+        let (mut leds, log) = leds();
+        leds.set(0, true);
+        leds.set(1, true);
+        leds.tick().unwrap();
+
+        assert_eq!(
+            vec![(1, Mode::Low), (2, Mode::Low), (0, Mode::High)],
+            take(&log)
+        );
+    }
+
+    #[test]
+    fn floats_previous_pins_before_driving_next_anode() {
+        let (mut leds, log) = leds();
+        // anode on pin 0, cathode on pin 2
+        leds.set(1, true);
+        // anode on pin 1, cathode on pin 0
+        leds.set(2, true);
+        leds.tick().unwrap();
+        take(&log);
+
+        leds.tick().unwrap();
+        assert_eq!(
+            vec![
+                (0, Mode::Floating),
+                (2, Mode::Floating),
+                (0, Mode::Low),
+                (1, Mode::High)
+            ],
+            take(&log)
+        );
+    }
+
+    #[test]
+    fn floats_previous_anode_before_driving_it_as_cathode() {
+        let (mut leds, log) = leds();
+        // anode on pin 1, cathode on pin 2
+        leds.set(3, true);
+        // anode on pin 2, cathodes on pins 0 and 1
+        leds.set(4, true);
+        leds.set(5, true);
+        leds.tick().unwrap();
+        leds.tick().unwrap();
+        take(&log);
+
+        leds.tick().unwrap();
+        assert_eq!(
+            vec![
+                (1, Mode::Floating),
+                (0, Mode::Low),
+                (1, Mode::Low),
+                (2, Mode::High)
+            ],
+            take(&log)
+        );
+    }
+
+    #[test]
+    fn anode_without_lit_leds_floats_everything() {
+        let (mut leds, log) = leds();
+        leds.set(1, true);
+        leds.tick().unwrap();
+        take(&log);
+
+        leds.tick().unwrap();
+        assert_eq!(vec![(0, Mode::Floating), (2, Mode::Floating)], take(&log));
+    }
+
+    #[test]
+    fn blank() {
+        let (mut leds, log) = leds();
+        leds.set(1, true);
+        leds.tick().unwrap();
+        take(&log);
+
+        leds.blank().unwrap();
+        assert_eq!(vec![(0, Mode::Floating), (2, Mode::Floating)], take(&log));
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn reports_failing_pin_then_lost_pin() {
+        let log = Log::default();
+        let pins = [0, 1].map(|index| Input {
+            index,
+            log: log.clone(),
+            fail: index == 1,
+        });
+        let mut leds = Charlieplex::new(pins);
+        leds.set(0, true);
+
+        assert_eq!(
+            Error::Pin(bank::Error {
+                index: 1,
+                error: "mode change failed"
+            }),
+            leds.tick().unwrap_err()
+        );
+        assert_eq!(Error::Lost(1), leds.blank().unwrap_err());
+    }
+}
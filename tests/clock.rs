@@ -0,0 +1,130 @@
+use switch_hal::clock::{Clock, Instant, Millis};
+use switch_hal::mock::MockClock;
+
+mod millis {
+    use super::*;
+
+    #[test]
+    fn elapsed() {
+        assert_eq!(5, Millis(15).millis_since(Millis(10)));
+    }
+
+    #[test]
+    fn earlier_in_future_is_zero() {
+        assert_eq!(0, Millis(10).millis_since(Millis(15)));
+    }
+}
+
+mod closure_clock {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn reports_closure_instant() {
+        let ticks = Cell::new(3);
+        let clock = || Millis(ticks.get());
+        let start = clock.now();
+        ticks.set(7);
+
+        assert_eq!(Millis(7), clock.now());
+        assert_eq!(4, clock.millis_since(start));
+    }
+}
+
+mod mock_clock {
+    use super::*;
+
+    #[test]
+    fn advanced_manually() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        clock.advance(20);
+        assert_eq!(20, clock.millis_since(start));
+
+        clock.set(5);
+        assert_eq!(Millis(5), clock.now());
+        assert_eq!(5, clock.millis());
+    }
+}
+
+#[cfg(feature = "fugit")]
+mod fugit_instant {
+    use super::*;
+
+    type Micros32 = fugit::Instant<u32, 1, 1_000_000>;
+    type Ticks64 = fugit::Instant<u64, 1, 32_768>;
+
+    #[test]
+    fn converts_to_millis() {
+        let start = Micros32::from_ticks(1_000);
+        assert_eq!(2, Micros32::from_ticks(3_500).millis_since(start));
+        assert_eq!(
+            1,
+            Ticks64::from_ticks(32_768).millis_since(Ticks64::from_ticks(32_735))
+        );
+    }
+
+    #[test]
+    fn handles_wrap_around() {
+        let start = Micros32::from_ticks(u32::MAX - 999);
+        assert_eq!(2, Micros32::from_ticks(1_000).millis_since(start));
+    }
+
+    #[test]
+    fn earlier_in_future_is_zero() {
+        let start = Micros32::from_ticks(5_000);
+        assert_eq!(0, Micros32::from_ticks(1_000).millis_since(start));
+    }
+}
+
+#[cfg(feature = "embedded-time")]
+mod embedded_time_instant {
+    use super::*;
+    use embedded_time::fraction::Fraction;
+
+    #[derive(Debug)]
+    struct Micros32;
+
+    impl embedded_time::Clock for Micros32 {
+        type T = u32;
+        const SCALING_FACTOR: Fraction = Fraction::new(1, 1_000_000);
+
+        fn try_now(&self) -> Result<embedded_time::Instant<Self>, embedded_time::clock::Error> {
+            Ok(embedded_time::Instant::new(0))
+        }
+    }
+
+    #[derive(Debug)]
+    struct Ticks64;
+
+    impl embedded_time::Clock for Ticks64 {
+        type T = u64;
+        const SCALING_FACTOR: Fraction = Fraction::new(1, 32_768);
+
+        fn try_now(&self) -> Result<embedded_time::Instant<Self>, embedded_time::clock::Error> {
+            Ok(embedded_time::Instant::new(0))
+        }
+    }
+
+    type Micros = embedded_time::Instant<Micros32>;
+    type Ticks = embedded_time::Instant<Ticks64>;
+
+    #[test]
+    fn converts_to_millis() {
+        let start = Micros::new(1_000);
+        assert_eq!(2, Micros::new(3_500).millis_since(start));
+        assert_eq!(1, Ticks::new(32_768).millis_since(Ticks::new(32_735)));
+    }
+
+    #[test]
+    fn handles_wrap_around() {
+        let start = Micros::new(u32::MAX - 999);
+        assert_eq!(2, Micros::new(1_000).millis_since(start));
+    }
+
+    #[test]
+    fn earlier_in_future_is_zero() {
+        let start = Micros::new(5_000);
+        assert_eq!(0, Micros::new(1_000).millis_since(start));
+    }
+}
@@ -0,0 +1,63 @@
+//! Fakes shared by the driver tests, which check the order of writes across several lines
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use embedded_hal::digital::v2::OutputPin;
+use switch_hal::OutputSwitch;
+
+/// Events from every fake sharing the log, in the order they happened
+pub type Log<E> = Rc<RefCell<Vec<E>>>;
+
+/// Removes and returns every event logged so far
+pub fn take<E>(log: &Log<E>) -> Vec<E> {
+    log.borrow_mut().drain(..).collect()
+}
+
+/// Logs `(line, level)` for every write, both as an `OutputPin` and as an `OutputSwitch`, where on is high
+pub struct Probe<L> {
+    line: L,
+    log: Log<(L, bool)>,
+}
+
+impl<L: Copy> Probe<L> {
+    pub fn new(line: L, log: &Log<(L, bool)>) -> Self {
+        Probe {
+            line,
+            log: log.clone(),
+        }
+    }
+
+    fn write(&self, level: bool) {
+        self.log.borrow_mut().push((self.line, level));
+    }
+}
+
+impl<L: Copy> OutputPin for Probe<L> {
+    type Error = &'static str;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.write(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.write(true);
+        Ok(())
+    }
+}
+
+impl<L: Copy> OutputSwitch for Probe<L> {
+    type Error = &'static str;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        self.write(true);
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.write(false);
+        Ok(())
+    }
+}
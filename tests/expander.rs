@@ -0,0 +1,254 @@
+use embedded_hal_mock::eh0::i2c::{Mock, Transaction};
+use embedded_hal_mock::eh0::MockError;
+use switch_hal::expander::{Mcp23017, Pcf8574};
+use switch_hal::port::{Latch, Port};
+use switch_hal::{InputSwitch, IntoSwitch, OutputSwitch};
+
+mod pcf8574 {
+    use super::*;
+
+    #[test]
+    fn output_writes_whole_port() {
+        let i2c = Mock::new(&[
+            Transaction::write(0x21, vec![0b1111_1110]),
+            Transaction::write(0x21, vec![0b1111_1010]),
+        ]);
+        let port = Port::with_outputs(Pcf8574::new(i2c, 0x21), 0xFF);
+        let mut first = port.output(0).into_active_low_switch();
+        let mut second = port.output(2).into_active_low_switch();
+        first.on().unwrap();
+        second.on().unwrap();
+
+        port.into_inner().into_inner().done();
+    }
+
+    #[test]
+    fn input_reads_whole_port() {
+        let i2c = Mock::new(&[Transaction::read(0x20, vec![0b1111_0111])]);
+        let port = Port::with_outputs(Pcf8574::new(i2c, 0x20), 0xFF);
+        let pressed = port.input(3).into_active_low_switch();
+        let released = port.input(4).into_active_low_switch();
+        port.refresh().unwrap();
+
+        assert!(pressed.is_active().unwrap());
+        assert!(!released.is_active().unwrap());
+        port.into_inner().into_inner().done();
+    }
+
+    #[test]
+    fn propagates_bus_errors() {
+        let i2c = Mock::new(&[Transaction::write(0x20, vec![0b0000_0001])
+            .with_error(MockError::Io(std::io::ErrorKind::Other))]);
+        let port = Port::new(Pcf8574::new(i2c, 0x20));
+        let mut led = port.output(0).into_active_high_switch();
+        led.on().expect_err("Expected bus error");
+
+        port.into_inner().into_inner().done();
+    }
+}
+
+mod mcp23017 {
+    use super::*;
+
+    #[test]
+    fn configures_directions_and_pull_ups() {
+        let i2c = Mock::new(&[
+            Transaction::write(0x27, vec![0x00, 0x0F, 0x80]),
+            Transaction::write(0x27, vec![0x0C, 0x0F, 0x00]),
+        ]);
+        let mut mcp = Mcp23017::new(i2c, 0x27);
+        mcp.set_directions(0x800F).unwrap();
+        mcp.set_pull_ups(0x000F).unwrap();
+
+        mcp.into_inner().done();
+    }
+
+    #[test]
+    fn output_writes_both_latches() {
+        let i2c = Mock::new(&[
+            Transaction::write(0x20, vec![0x14, 0x00, 0x02]),
+            Transaction::write(0x20, vec![0x14, 0x01, 0x02]),
+        ]);
+        let port = Port::new(Mcp23017::new(i2c, 0x20));
+        let mut gpb1 = port.output(9).into_active_high_switch();
+        let mut gpa0 = port.output(0).into_active_high_switch();
+        gpb1.on().unwrap();
+        gpa0.on().unwrap();
+
+        port.into_inner().into_inner().done();
+    }
+
+    #[test]
+    fn cached_outputs_avoid_read_modify_write() {
+        let i2c = Mock::new(&[Transaction::write(0x20, vec![0x14, 0x81, 0x00])]);
+        let port = Port::new(Mcp23017::new(i2c, 0x20));
+        port.set_latch(Latch::Deferred);
+        let mut gpa0 = port.output(0).into_active_high_switch();
+        let mut gpa7 = port.output(7).into_active_high_switch();
+        gpa0.on().unwrap();
+        gpa7.on().unwrap();
+        port.flush().unwrap();
+
+        port.into_inner().into_inner().done();
+    }
+
+    #[test]
+    fn input_reads_both_ports() {
+        let i2c = Mock::new(&[Transaction::write_read(0x20, vec![0x12], vec![0x00, 0x10])]);
+        let port = Port::new(Mcp23017::new(i2c, 0x20));
+        let gpb4 = port.input(12).into_active_high_switch();
+        let gpa4 = port.input(4).into_active_high_switch();
+        port.refresh().unwrap();
+
+        assert!(gpb4.is_active().unwrap());
+        assert!(!gpa4.is_active().unwrap());
+        port.into_inner().into_inner().done();
+    }
+}
+
+mod interrupts {
+    use super::*;
+    use switch_hal::interrupt::{InterruptSwitch, Trigger};
+
+    #[test]
+    fn configures_interrupt_on_change() {
+        let i2c = Mock::new(&[
+            // GPA0 falling only, GPB7 either edge
+            Transaction::write(0x20, vec![0x06, 0x01, 0x80]),
+            Transaction::write(0x20, vec![0x08, 0x01, 0x00]),
+            Transaction::write(0x20, vec![0x04, 0x01, 0x80]),
+        ]);
+        let port = Port::new(Mcp23017::new(i2c, 0x20));
+        let mut button = port.input(0).into_active_low_switch();
+        let mut door = port.input(15).into_active_high_switch();
+        button.trigger_on(Trigger::Activation);
+        button.enable_interrupt();
+        door.trigger_on(Trigger::Both);
+        door.enable_interrupt();
+        port.configure_interrupts().unwrap();
+
+        port.into_inner().into_inner().done();
+    }
+
+    #[test]
+    fn services_interrupt_with_captured_inputs() {
+        let i2c = Mock::new(&[Transaction::write_read(
+            0x20,
+            vec![0x0E],
+            vec![0x01, 0x00, 0xFE, 0xFF],
+        )]);
+        let port = Port::new(Mcp23017::new(i2c, 0x20));
+        let mut button = port.input(0).into_active_low_switch();
+
+        port.service_interrupts().unwrap();
+        assert!(button.check_interrupt());
+        assert!(button.is_active().unwrap());
+        button.clear_interrupt();
+        assert!(!button.check_interrupt());
+
+        port.into_inner().into_inner().done();
+    }
+
+    #[test]
+    fn interrupt_on_one_port_keeps_other_port_inputs() {
+        let i2c = Mock::new(&[
+            Transaction::write_read(0x20, vec![0x12], vec![0xFF, 0x01]),
+            // only GPA0 interrupted, so INTCAPB still holds a stale value
+            Transaction::write_read(0x20, vec![0x0E], vec![0x01, 0x00, 0xFE, 0x00]),
+        ]);
+        let port = Port::new(Mcp23017::new(i2c, 0x20));
+        let button = port.input(0).into_active_low_switch();
+        let door = port.input(8).into_active_high_switch();
+        port.refresh().unwrap();
+
+        assert_eq!(0x01, port.service_interrupts().unwrap());
+        assert!(button.is_active().unwrap());
+        assert!(door.is_active().unwrap());
+        assert_eq!(0x01FE, port.inputs());
+
+        port.into_inner().into_inner().done();
+    }
+}
+
+mod mcp23s17 {
+    use super::*;
+    use embedded_hal_mock::eh0::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh0::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use switch_hal::expander::{iocon, Mcp23s17};
+
+    fn chip_select(transfers: usize) -> PinMock {
+        let mut expectations = Vec::new();
+        for _ in 0..transfers {
+            expectations.push(PinTransaction::set(State::Low));
+            expectations.push(PinTransaction::set(State::High));
+        }
+        PinMock::new(&expectations)
+    }
+
+    fn done(mcp: Mcp23s17<SpiMock, PinMock>) {
+        let (mut spi, mut cs) = mcp.into_inner();
+        spi.done();
+        cs.done();
+    }
+
+    #[test]
+    fn enables_hardware_address() {
+        let spi = SpiMock::new(&[SpiTransaction::write(vec![0x46, 0x0A, 0x08])]);
+        let mut mcp = Mcp23s17::new(spi, chip_select(1), 3);
+        mcp.set_config(iocon::HAEN).unwrap();
+
+        done(mcp);
+    }
+
+    #[test]
+    fn batches_output_writes() {
+        let spi = SpiMock::new(&[
+            SpiTransaction::write(vec![0x40, 0x00, 0xFF, 0x00]),
+            SpiTransaction::write(vec![0x40, 0x14, 0x00, 0x03]),
+        ]);
+        let mut mcp = Mcp23s17::new(spi, chip_select(2), 0);
+        mcp.set_directions(0x00FF).unwrap();
+
+        let port = Port::new(mcp);
+        port.set_latch(Latch::Deferred);
+        let mut gpb0 = port.output(8).into_active_high_switch();
+        let mut gpb1 = port.output(9).into_active_high_switch();
+        gpb0.on().unwrap();
+        gpb1.on().unwrap();
+        port.flush().unwrap();
+
+        done(port.into_inner());
+    }
+
+    #[test]
+    fn reads_inputs() {
+        let spi = SpiMock::new(&[SpiTransaction::transfer(
+            vec![0x41, 0x12, 0x00, 0x00],
+            vec![0x00, 0x00, 0x04, 0x00],
+        )]);
+        let port = Port::new(Mcp23s17::new(spi, chip_select(1), 0));
+        let gpa2 = port.input(2).into_active_high_switch();
+        port.refresh().unwrap();
+
+        assert!(gpa2.is_active().unwrap());
+        done(port.into_inner());
+    }
+
+    #[test]
+    fn services_interrupts() {
+        let spi = SpiMock::new(&[SpiTransaction::transfer(
+            vec![0x41, 0x0E, 0x00, 0x00, 0x00, 0x00],
+            vec![0x00, 0x00, 0x00, 0x01, 0x00, 0x00],
+        )]);
+        let port = Port::new(Mcp23s17::new(spi, chip_select(1), 0));
+
+        assert_eq!(0x0100, port.service_interrupts().unwrap());
+        done(port.into_inner());
+    }
+
+    #[test]
+    #[should_panic(expected = "address is 3 bits")]
+    fn address_out_of_range_panics() {
+        Mcp23s17::new(SpiMock::new(&[]), chip_select(0), 8);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 extern crate switch_hal;
 
 use switch_hal::mock::{Pin, State};
@@ -0,0 +1,123 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::interrupt::{Edge, InterruptSwitch, Trigger};
+use switch_hal::mock::{Pin, State};
+use switch_hal::IntoSwitch;
+
+mod active_high_switch {
+    use super::*;
+
+    #[test]
+    fn activation_triggers_on_rising_edge() {
+        let mut button = Pin::with_state(State::Low).into_active_high_switch();
+        button.trigger_on(Trigger::Activation);
+
+        let pin = button.into_pin();
+        assert_eq!(Some(Edge::Rising), pin.edge());
+    }
+
+    #[test]
+    fn deactivation_triggers_on_falling_edge() {
+        let mut button = Pin::with_state(State::Low).into_active_high_switch();
+        button.trigger_on(Trigger::Deactivation);
+
+        let pin = button.into_pin();
+        assert_eq!(Some(Edge::Falling), pin.edge());
+    }
+
+    #[test]
+    fn both_triggers_on_either_edge() {
+        let mut button = Pin::with_state(State::Low).into_active_high_switch();
+        button.trigger_on(Trigger::Both);
+
+        let pin = button.into_pin();
+        assert_eq!(Some(Edge::RisingFalling), pin.edge());
+    }
+}
+
+mod active_low_switch {
+    use super::*;
+
+    #[test]
+    fn activation_triggers_on_falling_edge() {
+        let mut button = Pin::with_state(State::High).into_active_low_switch();
+        button.trigger_on(Trigger::Activation);
+
+        let pin = button.into_pin();
+        assert_eq!(Some(Edge::Falling), pin.edge());
+    }
+
+    #[test]
+    fn deactivation_triggers_on_rising_edge() {
+        let mut button = Pin::with_state(State::High).into_active_low_switch();
+        button.trigger_on(Trigger::Deactivation);
+
+        let pin = button.into_pin();
+        assert_eq!(Some(Edge::Rising), pin.edge());
+    }
+
+    #[test]
+    fn both_triggers_on_either_edge() {
+        let mut button = Pin::with_state(State::High).into_active_low_switch();
+        button.trigger_on(Trigger::Both);
+
+        let pin = button.into_pin();
+        assert_eq!(Some(Edge::RisingFalling), pin.edge());
+    }
+}
+
+mod isr_flow {
+    use super::*;
+
+    fn handle_interrupt<S: InterruptSwitch>(button: &mut S) -> bool {
+        if button.check_interrupt() {
+            button.clear_interrupt();
+            true
+        } else {
+            false
+        }
+    }
+
+    #[test]
+    fn press_raises_and_clears_interrupt() {
+        let mut button = Pin::with_state(State::High).into_active_low_switch();
+        button.trigger_on(Trigger::Activation);
+        button.enable_interrupt();
+        assert_eq!(false, handle_interrupt(&mut button));
+
+        let mut pin = button.into_pin();
+        pin.set_state(State::Low);
+        let mut button = pin.into_active_low_switch();
+
+        assert_eq!(true, handle_interrupt(&mut button));
+        assert_eq!(false, button.check_interrupt());
+    }
+
+    #[test]
+    fn release_does_not_raise_activation_interrupt() {
+        let mut button = Pin::with_state(State::Low).into_active_low_switch();
+        button.trigger_on(Trigger::Activation);
+        button.enable_interrupt();
+
+        let mut pin = button.into_pin();
+        pin.set_state(State::High);
+        let mut button = pin.into_active_low_switch();
+
+        assert_eq!(false, handle_interrupt(&mut button));
+    }
+
+    #[test]
+    fn disabled_interrupt_is_not_raised() {
+        let mut button = Pin::with_state(State::High).into_active_low_switch();
+        button.trigger_on(Trigger::Activation);
+        button.enable_interrupt();
+        button.disable_interrupt();
+
+        let mut pin = button.into_pin();
+        assert_eq!(false, pin.is_interrupt_enabled());
+        pin.set_state(State::Low);
+        let mut button = pin.into_active_low_switch();
+
+        assert_eq!(false, handle_interrupt(&mut button));
+    }
+}
@@ -0,0 +1,107 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::mock::{Pin, State};
+use switch_hal::{IntoSwitch, Invert, Inverted};
+
+mod input_switch {
+    use super::*;
+    use switch_hal::InputSwitch;
+
+    #[test]
+    fn inactive_when_wrapped_switch_active() {
+        let button = Inverted::new(Pin::with_state(State::High).into_active_high_switch());
+        assert_eq!(false, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn active_when_wrapped_switch_inactive() {
+        let button = Inverted::new(Pin::with_state(State::Low).into_active_high_switch());
+        assert_eq!(true, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn propagates_errors_from_pin() {
+        let button = Inverted::new(Pin::new().into_active_high_switch());
+        button
+            .is_active()
+            .expect_err("Expected uninitialized error");
+    }
+}
+
+mod output_switch {
+    use super::*;
+    use embedded_hal::digital::v2::InputPin;
+    use switch_hal::{OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch};
+
+    #[test]
+    fn on_turns_wrapped_switch_off() {
+        let mut led = Inverted::new(Pin::new().into_active_high_switch());
+        led.on().unwrap();
+
+        let pin = led.into_inner().into_pin();
+        assert_eq!(true, pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn off_turns_wrapped_switch_on() {
+        let mut led = Inverted::new(Pin::new().into_active_high_switch());
+        led.off().unwrap();
+
+        let pin = led.into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn is_on_when_wrapped_switch_off() {
+        let mut led = Inverted::new(Pin::new().into_active_low_switch());
+        led.on().unwrap();
+
+        assert_eq!(true, led.is_on().unwrap());
+        assert_eq!(false, led.is_off().unwrap());
+    }
+
+    #[test]
+    fn is_toggleable() {
+        let mut led = Inverted::new(Pin::new().into_active_high_switch());
+        led.off().unwrap();
+
+        led.toggle().unwrap();
+
+        assert_eq!(true, led.is_on().unwrap());
+    }
+}
+
+mod invert {
+    use super::*;
+    use switch_hal::{ActiveHigh, ActiveLow, InputSwitch, Switch};
+
+    #[test]
+    fn wraps_any_switch() {
+        let button: Inverted<Switch<Pin, ActiveHigh>> =
+            Invert::inverted(Pin::with_state(State::High).into_active_high_switch());
+        assert_eq!(false, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn double_inversion_unwraps() {
+        let button = Pin::with_state(State::High).into_active_high_switch();
+        let button: Switch<Pin, ActiveHigh> = Invert::inverted(button).inverted();
+        assert_eq!(true, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn active_high_switch_becomes_active_low() {
+        let button: Switch<Pin, ActiveLow> = Pin::with_state(State::High)
+            .into_active_high_switch()
+            .inverted();
+        assert_eq!(false, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn active_low_switch_becomes_active_high() {
+        let button: Switch<Pin, ActiveHigh> = Pin::with_state(State::High)
+            .into_active_low_switch()
+            .inverted();
+        assert_eq!(true, button.is_active().unwrap());
+    }
+}
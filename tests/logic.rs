@@ -0,0 +1,130 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::logic::{all, any, not, xor, Error};
+use switch_hal::mock::{MockError, Pin, State};
+use switch_hal::{ActiveHigh, InputSwitch, IntoSwitch, Switch};
+
+fn switch(active: bool) -> Switch<Pin, ActiveHigh> {
+    let state = if active { State::High } else { State::Low };
+    Pin::with_state(state).into_active_high_switch()
+}
+
+fn broken() -> Switch<Pin, ActiveHigh> {
+    Pin::new().into_active_high_switch()
+}
+
+mod all {
+    use super::*;
+
+    #[test]
+    fn truth_table() {
+        assert_eq!(
+            false,
+            all(switch(false), switch(false)).is_active().unwrap()
+        );
+        assert_eq!(false, all(switch(false), switch(true)).is_active().unwrap());
+        assert_eq!(false, all(switch(true), switch(false)).is_active().unwrap());
+        assert_eq!(true, all(switch(true), switch(true)).is_active().unwrap());
+    }
+
+    #[test]
+    fn short_circuits_when_first_inactive() {
+        assert_eq!(false, all(switch(false), broken()).is_active().unwrap());
+    }
+
+    #[test]
+    fn reports_failing_switch() {
+        assert_eq!(
+            Error::First(MockError::Uninitialized),
+            all(broken(), switch(true)).is_active().unwrap_err()
+        );
+        assert_eq!(
+            Error::Second(MockError::Uninitialized),
+            all(switch(true), broken()).is_active().unwrap_err()
+        );
+    }
+}
+
+mod any {
+    use super::*;
+
+    #[test]
+    fn truth_table() {
+        assert_eq!(
+            false,
+            any(switch(false), switch(false)).is_active().unwrap()
+        );
+        assert_eq!(true, any(switch(false), switch(true)).is_active().unwrap());
+        assert_eq!(true, any(switch(true), switch(false)).is_active().unwrap());
+        assert_eq!(true, any(switch(true), switch(true)).is_active().unwrap());
+    }
+
+    #[test]
+    fn short_circuits_when_first_active() {
+        assert_eq!(true, any(switch(true), broken()).is_active().unwrap());
+    }
+
+    #[test]
+    fn reports_failing_switch() {
+        assert_eq!(
+            Error::Second(MockError::Uninitialized),
+            any(switch(false), broken()).is_active().unwrap_err()
+        );
+    }
+}
+
+mod xor {
+    use super::*;
+
+    #[test]
+    fn truth_table() {
+        assert_eq!(
+            false,
+            xor(switch(false), switch(false)).is_active().unwrap()
+        );
+        assert_eq!(true, xor(switch(false), switch(true)).is_active().unwrap());
+        assert_eq!(true, xor(switch(true), switch(false)).is_active().unwrap());
+        assert_eq!(false, xor(switch(true), switch(true)).is_active().unwrap());
+    }
+
+    #[test]
+    fn reports_failing_switch() {
+        assert_eq!(
+            Error::First(MockError::Uninitialized),
+            xor(broken(), switch(true)).is_active().unwrap_err()
+        );
+    }
+}
+
+mod not {
+    use super::*;
+
+    #[test]
+    fn inverts() {
+        assert_eq!(true, not(switch(false)).is_active().unwrap());
+        assert_eq!(false, not(switch(true)).is_active().unwrap());
+    }
+}
+
+mod composition {
+    use super::*;
+
+    #[test]
+    fn nests_heterogeneous_switches() {
+        let estop_a = Pin::with_state(State::Low).into_active_low_switch();
+        let estop_b = switch(true);
+        let door = not(switch(false));
+
+        let may_run = all(all(estop_a, estop_b), door);
+        assert_eq!(true, may_run.is_active().unwrap());
+    }
+
+    #[test]
+    fn nested_errors_identify_switch() {
+        let may_run = all(switch(true), any(switch(false), broken()));
+        assert_eq!(
+            Error::Second(Error::Second(MockError::Uninitialized)),
+            may_run.is_active().unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use common::{take, Probe};
+use switch_hal::matrix::{Error, Matrix};
+use switch_hal::{bank, OutputSwitch};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Line {
+    Row(usize),
+    Column(usize),
+}
+
+type Log = common::Log<(Line, bool)>;
+
+fn matrix() -> (Matrix<Probe<Line>, Probe<Line>, 2, 3>, Log) {
+    let log = Log::default();
+    let rows = [0, 1].map(|row| Probe::new(Line::Row(row), &log));
+    let columns = [0, 1, 2].map(|column| Probe::new(Line::Column(column), &log));
+    (Matrix::new(rows, columns), log)
+}
+
+mod scanning {
+    use super::*;
+    use Line::*;
+
+    #[test]
+    fn first_tick_shows_first_row() {
+        let (mut matrix, log) = matrix();
+        matrix.set_pixel(1, 0, true);
+        matrix.tick().unwrap();
+
+        assert_eq!(
+            vec![
+                (Row(1), false),
+                (Column(0), false),
+                (Column(1), true),
+                (Column(2), false),
+                (Row(0), true)
+            ],
+            take(&log)
+        );
+    }
+
+    #[test]
+    fn blanks_previous_row_before_changing_columns() {
+        let (mut matrix, log) = matrix();
+        matrix.set_pixel(2, 1, true);
+        matrix.tick().unwrap();
+        take(&log);
+
+        matrix.tick().unwrap();
+        assert_eq!(
+            vec![
+                (Row(0), false),
+                (Column(0), false),
+                (Column(1), false),
+                (Column(2), true),
+                (Row(1), true)
+            ],
+            take(&log)
+        );
+    }
+
+    #[test]
+    fn wraps_to_first_row() {
+        let (mut matrix, log) = matrix();
+        matrix.tick().unwrap();
+        matrix.tick().unwrap();
+        take(&log);
+
+        matrix.tick().unwrap();
+        assert_eq!(Some(&(Row(0), true)), take(&log).last());
+    }
+
+    #[test]
+    fn set_frame() {
+        let (mut matrix, _log) = matrix();
+        matrix.set_frame([[true, false, true], [false, true, false]]);
+        assert!(matrix.pixel(2, 0));
+        assert!(matrix.pixel(1, 1));
+
+        matrix.clear();
+        assert!(!matrix.pixel(2, 0));
+    }
+
+    #[test]
+    fn blank() {
+        let (mut matrix, log) = matrix();
+        matrix.blank().unwrap();
+        assert_eq!(vec![(Row(0), false), (Row(1), false)], take(&log));
+    }
+}
+
+mod brightness {
+    use super::*;
+    use Line::*;
+
+    #[test]
+    fn row_turns_off_after_on_time() {
+        let (mut matrix, log) = matrix();
+        matrix.set_row_timing(1, 3);
+        matrix.tick().unwrap();
+        take(&log);
+
+        matrix.tick().unwrap();
+        assert_eq!(vec![(Row(0), false)], take(&log));
+
+        matrix.tick().unwrap();
+        assert_eq!(Vec::<(Line, bool)>::new(), take(&log));
+
+        matrix.tick().unwrap();
+        assert_eq!(Some(&(Row(1), true)), take(&log).last());
+    }
+
+    #[test]
+    fn full_on_time_never_turns_off_mid_slot() {
+        let (mut matrix, log) = matrix();
+        matrix.set_row_timing(2, 2);
+        matrix.tick().unwrap();
+        take(&log);
+
+        matrix.tick().unwrap();
+        assert_eq!(Vec::<(Line, bool)>::new(), take(&log));
+    }
+
+    #[test]
+    fn zero_on_time_never_lights_rows() {
+        let (mut matrix, log) = matrix();
+        matrix.set_row_timing(0, 2);
+        matrix.tick().unwrap();
+
+        assert!(!take(&log).contains(&(Row(0), true)));
+    }
+}
+
+mod errors {
+    use super::*;
+    use switch_hal::mock::Pin;
+    use switch_hal::IntoSwitch;
+
+    struct Broken;
+
+    impl OutputSwitch for Broken {
+        type Error = &'static str;
+
+        fn on(&mut self) -> Result<(), Self::Error> {
+            Err("write failed")
+        }
+
+        fn off(&mut self) -> Result<(), Self::Error> {
+            Err("write failed")
+        }
+    }
+
+    #[test]
+    fn reports_failing_row() {
+        let mut matrix = Matrix::new([Broken], [Pin::new().into_active_high_switch()]);
+        assert_eq!(
+            Error::Row(bank::Error {
+                index: 0,
+                error: "write failed"
+            }),
+            matrix.tick().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn reports_failing_column() {
+        let mut matrix = Matrix::new([Pin::new().into_active_high_switch()], [Broken]);
+        assert_eq!(
+            Error::Column(bank::Error {
+                index: 0,
+                error: "write failed"
+            }),
+            matrix.tick().unwrap_err()
+        );
+    }
+}
+
+mod size {
+    use super::*;
+    use switch_hal::mock::Pin;
+    use switch_hal::{ActiveHigh, IntoSwitch, Switch};
+
+    #[test]
+    #[should_panic(expected = "at least one row")]
+    fn no_rows_panics() {
+        let rows: [Switch<Pin, ActiveHigh>; 0] = [];
+        Matrix::new(rows, [Pin::new().into_active_high_switch()]);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 extern crate switch_hal;
 
 use switch_hal::mock;
@@ -147,3 +149,36 @@ mod active_low_switch {
         assert_eq!(true, pin.is_low().unwrap());
     }
 }
+
+mod dimmable_switch {
+    use super::*;
+    use embedded_hal::PwmPin;
+    use switch_hal::{ActiveHigh, ActiveLow, DimmableOutputSwitch, Switch};
+
+    #[test]
+    fn active_high_duty_follows_brightness() {
+        let mut led = Switch::<_, ActiveHigh>::new(mock::Pwm::new(1000));
+        led.set_brightness(51).unwrap();
+
+        let pwm = led.into_pin();
+        assert_eq!(200, pwm.get_duty());
+    }
+
+    #[test]
+    fn active_low_duty_is_inverted() {
+        let mut led = Switch::<_, ActiveLow>::new(mock::Pwm::new(1000));
+        led.set_brightness(51).unwrap();
+
+        let pwm = led.into_pin();
+        assert_eq!(800, pwm.get_duty());
+    }
+
+    #[test]
+    fn full_brightness_is_max_duty() {
+        let mut led = Switch::<_, ActiveHigh>::new(mock::Pwm::new(u16::MAX));
+        led.set_brightness(255).unwrap();
+
+        let pwm = led.into_pin();
+        assert_eq!(u16::MAX, pwm.get_duty());
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use switch_hal::mock::{Pin, State};
 use switch_hal::IntoSwitch;
 
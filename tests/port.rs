@@ -0,0 +1,233 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::interrupt::{InterruptSwitch, Trigger};
+use switch_hal::port::{
+    InputPortDriver, InterruptConfig, InterruptPortDriver, InterruptStatus, Latch,
+    OutputPortDriver, Port,
+};
+use switch_hal::{
+    InputSwitch, IntoSwitch, OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch,
+};
+
+/// Records every write, unless writes fail, and returns a fixed input
+#[derive(Default)]
+struct Device {
+    writes: Vec<u32>,
+    fail_writes: bool,
+    inputs: u32,
+    reads: usize,
+    config: Option<InterruptConfig>,
+    status: InterruptStatus,
+}
+
+impl OutputPortDriver for Device {
+    type Error = &'static str;
+
+    fn write(&mut self, outputs: u32) -> Result<(), Self::Error> {
+        if self.fail_writes {
+            return Err("write failed");
+        }
+        self.writes.push(outputs);
+        Ok(())
+    }
+}
+
+impl InputPortDriver for Device {
+    type Error = &'static str;
+
+    fn read(&mut self) -> Result<u32, Self::Error> {
+        self.reads += 1;
+        Ok(self.inputs)
+    }
+}
+
+impl InterruptPortDriver for Device {
+    fn configure_interrupts(&mut self, config: InterruptConfig) -> Result<(), Self::Error> {
+        self.config = Some(config);
+        Ok(())
+    }
+
+    fn read_interrupts(&mut self) -> Result<InterruptStatus, Self::Error> {
+        Ok(core::mem::take(&mut self.status))
+    }
+}
+
+mod outputs {
+    use super::*;
+
+    #[test]
+    fn immediate_latch_writes_every_change() {
+        let port = Port::new(Device::default());
+        let mut first = port.output(0).into_active_high_switch();
+        let mut second = port.output(3).into_active_high_switch();
+        first.on().unwrap();
+        second.on().unwrap();
+        first.off().unwrap();
+
+        assert_eq!(vec![0b0001, 0b1001, 0b1000], port.into_inner().writes);
+    }
+
+    #[test]
+    fn failed_write_keeps_cached_outputs() {
+        let device = Device {
+            fail_writes: true,
+            ..Device::default()
+        };
+        let port = Port::with_outputs(device, 0b10);
+        let mut led = port.output(0).into_active_high_switch();
+
+        assert_eq!(Err("write failed"), led.on());
+        assert_eq!(false, led.is_on().unwrap());
+        assert_eq!(0b10, port.outputs());
+    }
+
+    #[test]
+    fn active_low_outputs_clear_bit_when_on() {
+        let port = Port::with_outputs(Device::default(), 0b11);
+        let mut led = port.output(1).into_active_low_switch();
+        led.on().unwrap();
+
+        assert_eq!(vec![0b01], port.into_inner().writes);
+    }
+
+    #[test]
+    fn deferred_latch_writes_on_flush() {
+        let port = Port::new(Device::default());
+        port.set_latch(Latch::Deferred);
+        let mut first = port.output(0).into_active_high_switch();
+        let mut second = port.output(1).into_active_high_switch();
+        first.on().unwrap();
+        second.on().unwrap();
+        assert_eq!(0b11, port.outputs());
+
+        port.flush().unwrap();
+        assert_eq!(vec![0b11], port.into_inner().writes);
+    }
+
+    #[test]
+    fn stateful_reads_cache() {
+        let port = Port::new(Device::default());
+        let mut led = port.output(2).into_active_low_switch();
+        led.on().unwrap();
+
+        assert_eq!(true, led.is_on().unwrap());
+        assert_eq!(0, port.outputs());
+    }
+
+    #[test]
+    fn toggleable() {
+        let port = Port::new(Device::default());
+        let mut led = port.output(4).into_active_high_switch();
+        led.toggle().unwrap();
+
+        assert_eq!(true, led.is_on().unwrap());
+        assert_eq!(0b1_0000, port.outputs());
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 32 pins")]
+    fn bit_out_of_range_panics() {
+        let port = Port::new(Device::default());
+        port.output(40);
+    }
+}
+
+mod inputs {
+    use super::*;
+    use switch_hal::InputSwitch;
+
+    #[test]
+    fn reads_snapshot() {
+        let port = Port::new(Device {
+            inputs: 0b10,
+            ..Device::default()
+        });
+        let first = port.input(0).into_active_high_switch();
+        let second = port.input(1).into_active_high_switch();
+        port.refresh().unwrap();
+
+        assert_eq!(false, first.is_active().unwrap());
+        assert_eq!(true, second.is_active().unwrap());
+        assert_eq!(1, port.into_inner().reads);
+    }
+
+    #[test]
+    fn active_low_inputs() {
+        let port = Port::new(Device {
+            inputs: 0b10,
+            ..Device::default()
+        });
+        let button = port.input(0).into_active_low_switch();
+        port.refresh().unwrap();
+
+        assert_eq!(true, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn inputs_are_low_until_refreshed() {
+        let port = Port::new(Device {
+            inputs: 0b1,
+            ..Device::default()
+        });
+        let button = port.input(0).into_active_high_switch();
+
+        assert_eq!(false, button.is_active().unwrap());
+        assert_eq!(0, port.into_inner().reads);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 32 pins")]
+    fn bit_out_of_range_panics() {
+        let port = Port::new(Device::default());
+        port.input(32);
+    }
+}
+
+mod interrupts {
+    use super::*;
+
+    #[test]
+    fn configuration_is_cached_until_written() {
+        let port = Port::new(Device::default());
+        let mut button = port.input(1).into_active_low_switch();
+        let mut door = port.input(4).into_active_high_switch();
+        button.trigger_on(Trigger::Activation);
+        button.enable_interrupt();
+        door.trigger_on(Trigger::Both);
+        door.enable_interrupt();
+        door.disable_interrupt();
+        port.configure_interrupts().unwrap();
+
+        let expected = InterruptConfig {
+            enabled: 0b00010,
+            rising: 0b10000,
+            falling: 0b10010,
+        };
+        assert_eq!(Some(expected), port.into_inner().config);
+    }
+
+    #[test]
+    fn service_latches_pending_until_cleared() {
+        let device = Device {
+            status: InterruptStatus {
+                pending: 0b10,
+                captured: 0b01,
+            },
+            ..Device::default()
+        };
+        let port = Port::new(device);
+        let mut button = port.input(1).into_active_low_switch();
+        let other = port.input(0).into_active_low_switch();
+
+        assert_eq!(0b10, port.service_interrupts().unwrap());
+        assert_eq!(true, button.check_interrupt());
+        assert_eq!(false, other.check_interrupt());
+        assert_eq!(true, button.is_active().unwrap());
+
+        // nothing new on the device, but the interrupt was not cleared yet
+        assert_eq!(0b10, port.service_interrupts().unwrap());
+        button.clear_interrupt();
+        assert_eq!(false, button.check_interrupt());
+        assert_eq!(0, port.service_interrupts().unwrap());
+    }
+}
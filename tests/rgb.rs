@@ -0,0 +1,164 @@
+use embedded_hal::digital::v2::InputPin;
+use embedded_hal::PwmPin;
+use switch_hal::mock::{Pin, Pwm};
+use switch_hal::rgb::{Bicolor, BicolorLed, Color, Error, RgbLed};
+use switch_hal::{IntoSwitch, OutputSwitch};
+
+/// Returns which channels drive their pin high
+fn high<const N: usize>(pins: [Pin; N]) -> [bool; N] {
+    pins.map(|pin| pin.is_high().unwrap())
+}
+
+mod rgb_led {
+    use super::*;
+
+    #[test]
+    fn common_cathode() {
+        let mut led = RgbLed::new(
+            Pin::new().into_active_high_switch(),
+            Pin::new().into_active_high_switch(),
+            Pin::new().into_active_high_switch(),
+        );
+        led.set_color(Color::Magenta).unwrap();
+
+        let (r, g, b) = led.into_inner();
+        assert_eq!(
+            [true, false, true],
+            high([r.into_pin(), g.into_pin(), b.into_pin()])
+        );
+    }
+
+    #[test]
+    fn common_anode() {
+        let mut led = RgbLed::new(
+            Pin::new().into_active_low_switch(),
+            Pin::new().into_active_low_switch(),
+            Pin::new().into_active_low_switch(),
+        );
+        led.set_color(Color::Yellow).unwrap();
+
+        let (r, g, b) = led.into_inner();
+        assert_eq!(
+            [false, false, true],
+            high([r.into_pin(), g.into_pin(), b.into_pin()])
+        );
+    }
+
+    #[test]
+    fn mixed_polarity() {
+        let mut led = RgbLed::new(
+            Pin::new().into_active_low_switch(),
+            Pin::new().into_active_high_switch(),
+            Pin::new().into_active_high_switch(),
+        );
+        led.set_color(Color::White).unwrap();
+
+        let (r, g, b) = led.into_inner();
+        assert_eq!(
+            [false, true, true],
+            high([r.into_pin(), g.into_pin(), b.into_pin()])
+        );
+    }
+
+    #[test]
+    fn every_color_has_distinct_channels() {
+        let colors = [
+            Color::Off,
+            Color::Red,
+            Color::Green,
+            Color::Blue,
+            Color::Yellow,
+            Color::Cyan,
+            Color::Magenta,
+            Color::White,
+        ];
+        for (i, a) in colors.iter().enumerate() {
+            for b in &colors[i + 1..] {
+                assert_ne!(a.channels(), b.channels());
+            }
+        }
+    }
+
+    #[test]
+    fn pwm_mixing() {
+        let mut led = RgbLed::new(
+            Pwm::new(255).into_active_high_switch(),
+            Pwm::new(255).into_active_high_switch(),
+            Pwm::new(255).into_active_low_switch(),
+        );
+        led.set_rgb(255, 165, 0).unwrap();
+
+        let (r, g, b) = led.into_inner();
+        assert_eq!(
+            [255, 165, 255],
+            [
+                r.into_pin().get_duty(),
+                g.into_pin().get_duty(),
+                b.into_pin().get_duty()
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_failing_channel() {
+        struct Broken;
+
+        impl OutputSwitch for Broken {
+            type Error = &'static str;
+
+            fn on(&mut self) -> Result<(), Self::Error> {
+                Err("write failed")
+            }
+
+            fn off(&mut self) -> Result<(), Self::Error> {
+                Err("write failed")
+            }
+        }
+
+        let mut led = RgbLed::new(
+            Pin::new().into_active_high_switch(),
+            Broken,
+            Pin::new().into_active_high_switch(),
+        );
+        assert_eq!(
+            Error::Green("write failed"),
+            led.set_color(Color::Green).unwrap_err()
+        );
+    }
+}
+
+mod bicolor_led {
+    use super::*;
+
+    #[test]
+    fn set_color() {
+        let mut led = BicolorLed::new(
+            Pin::new().into_active_high_switch(),
+            Pin::new().into_active_low_switch(),
+        );
+        led.set_color(Bicolor::Red).unwrap();
+
+        let (r, g) = led.into_inner();
+        assert_eq!([true, true], high([r.into_pin(), g.into_pin()]));
+    }
+
+    #[test]
+    fn yellow_lights_both_channels() {
+        assert_eq!((true, true), Bicolor::Yellow.channels());
+    }
+
+    #[test]
+    fn pwm_mixing() {
+        let mut led = BicolorLed::new(
+            Pwm::new(100).into_active_high_switch(),
+            Pwm::new(100).into_active_high_switch(),
+        );
+        led.set_mix(255, 51).unwrap();
+
+        let (r, g) = led.into_inner();
+        assert_eq!(
+            [100, 20],
+            [r.into_pin().get_duty(), g.into_pin().get_duty()]
+        );
+    }
+}
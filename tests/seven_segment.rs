@@ -0,0 +1,215 @@
+use embedded_hal::digital::v2::InputPin;
+use switch_hal::mock::Pin;
+use switch_hal::seven_segment::{glyph, Error, Multiplexed, SevenSegment, DP, HEX_DIGITS};
+use switch_hal::{ActiveHigh, ActiveLow, IntoSwitch, Switch};
+
+/// Reads back the pattern driven onto the pins, where a high pin is a set bit
+fn pattern(pins: [Pin; 8]) -> u8 {
+    pins.iter()
+        .enumerate()
+        .filter(|(_, pin)| pin.is_high().unwrap())
+        .fold(0, |bits, (index, _)| bits | 1 << index)
+}
+
+fn pins<const N: usize>() -> [Pin; N] {
+    [(); N].map(|_| Pin::new())
+}
+
+mod font {
+    use super::*;
+
+    #[test]
+    fn hex_digits() {
+        for (value, c) in "0123456789AbCdEF".chars().enumerate() {
+            assert_eq!(Some(HEX_DIGITS[value]), glyph(c));
+        }
+    }
+
+    #[test]
+    fn uppercase_hex_digits() {
+        for (value, c) in "ABCDEF".chars().enumerate() {
+            assert_eq!(Some(HEX_DIGITS[10 + value]), glyph(c));
+        }
+    }
+
+    #[test]
+    fn unsupported_character() {
+        assert_eq!(None, glyph('M'));
+    }
+}
+
+mod single_digit {
+    use super::*;
+
+    #[test]
+    fn common_cathode() {
+        let mut display = SevenSegment::new(pins().map(|pin| pin.into_active_high_switch()));
+        display.display('1', false).unwrap();
+
+        let pins = display.into_inner().map(|s| s.into_pin());
+        assert_eq!(0b0000_0110, pattern(pins));
+    }
+
+    #[test]
+    fn common_anode() {
+        let mut display = SevenSegment::new(pins().map(|pin| pin.into_active_low_switch()));
+        display.display('1', false).unwrap();
+
+        let pins = display.into_inner().map(|s| s.into_pin());
+        assert_eq!(!0b0000_0110, pattern(pins));
+    }
+
+    #[test]
+    fn decimal_point() {
+        let mut display = SevenSegment::new(pins().map(|pin| pin.into_active_high_switch()));
+        display.display('0', true).unwrap();
+
+        let pins = display.into_inner().map(|s| s.into_pin());
+        assert_eq!(HEX_DIGITS[0] | DP, pattern(pins));
+    }
+
+    #[test]
+    fn clear() {
+        let mut display = SevenSegment::new(pins().map(|pin| pin.into_active_high_switch()));
+        display.display('8', true).unwrap();
+        display.clear().unwrap();
+
+        let pins = display.into_inner().map(|s| s.into_pin());
+        assert_eq!(0, pattern(pins));
+    }
+
+    #[test]
+    fn unsupported_character() {
+        let mut display = SevenSegment::new(pins().map(|pin| pin.into_active_high_switch()));
+        assert_eq!(
+            Error::Unsupported('W'),
+            display.display('W', false).unwrap_err()
+        );
+    }
+}
+
+mod multiplexed {
+    use super::*;
+
+    type Display = Multiplexed<Switch<Pin, ActiveHigh>, Switch<Pin, ActiveLow>, 3>;
+
+    fn display() -> Display {
+        Multiplexed::new(
+            pins().map(|pin| pin.into_active_high_switch()),
+            pins().map(|pin| pin.into_active_low_switch()),
+        )
+    }
+
+    /// Returns the segment pattern and which digits are selected.
+    /// Digit selects that were never written are not selected.
+    fn snapshot(display: Display) -> (u8, [bool; 3]) {
+        let (segments, digits) = display.into_inner();
+        let segments = pattern(segments.map(|s| s.into_pin()));
+        let digits = digits.map(|d| d.into_pin().is_low().unwrap_or(false));
+        (segments, digits)
+    }
+
+    #[test]
+    fn first_tick_shows_first_digit() {
+        let mut display = display();
+        display.set_text("123").unwrap();
+        display.tick().unwrap();
+
+        assert_eq!((HEX_DIGITS[1], [true, false, false]), snapshot(display));
+    }
+
+    #[test]
+    fn tick_advances_digit() {
+        let mut display = display();
+        display.set_text("123").unwrap();
+        display.tick().unwrap();
+        display.tick().unwrap();
+
+        assert_eq!((HEX_DIGITS[2], [false, true, false]), snapshot(display));
+    }
+
+    #[test]
+    fn tick_wraps_around() {
+        let mut display = display();
+        display.set_text("123").unwrap();
+        for _ in 0..4 {
+            display.tick().unwrap();
+        }
+
+        assert_eq!((HEX_DIGITS[1], [true, false, false]), snapshot(display));
+    }
+
+    #[test]
+    fn decimal_point_attaches_to_previous_digit() {
+        let mut display = display();
+        display.set_text("1.23").unwrap();
+        display.tick().unwrap();
+
+        assert_eq!(
+            (HEX_DIGITS[1] | DP, [true, false, false]),
+            snapshot(display)
+        );
+    }
+
+    #[test]
+    fn decimal_point_of_ignored_character_is_ignored() {
+        let mut display = display();
+        display.set_text("1234.").unwrap();
+        for _ in 0..3 {
+            display.tick().unwrap();
+        }
+
+        assert_eq!((HEX_DIGITS[3], [false, false, true]), snapshot(display));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one digit")]
+    fn no_digits_panics() {
+        Multiplexed::<_, Switch<Pin, ActiveLow>, 0>::new(
+            pins().map(|pin| pin.into_active_high_switch()),
+            [],
+        );
+    }
+
+    #[test]
+    fn short_text_blanks_remaining_digits() {
+        let mut display = display();
+        display.set_text("1").unwrap();
+        display.tick().unwrap();
+        display.tick().unwrap();
+
+        assert_eq!((0, [false, true, false]), snapshot(display));
+    }
+
+    #[test]
+    fn set_digit() {
+        let mut display = display();
+        display.set_digit(0, 'F', true).unwrap();
+        display.tick().unwrap();
+
+        assert_eq!(
+            (HEX_DIGITS[0xF] | DP, [true, false, false]),
+            snapshot(display)
+        );
+    }
+
+    #[test]
+    fn unsupported_character_leaves_buffer_unchanged() {
+        let mut display = display();
+        display.set_text("123").unwrap();
+        assert_eq!(Error::Unsupported('W'), display.set_text("1W").unwrap_err());
+        display.tick().unwrap();
+
+        assert_eq!((HEX_DIGITS[1], [true, false, false]), snapshot(display));
+    }
+
+    #[test]
+    fn blank() {
+        let mut display = display();
+        display.tick().unwrap();
+        display.blank().unwrap();
+
+        let (_, digits) = snapshot(display);
+        assert_eq!([false; 3], digits);
+    }
+}
@@ -0,0 +1,275 @@
+mod common;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use common::{take, Probe};
+use switch_hal::port::{Latch, Port};
+use switch_hal::shift_register::{Hc165, Hc595};
+use switch_hal::{InputSwitch, IntoSwitch, OutputSwitch};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Line {
+    Data,
+    Clock,
+    Latch,
+}
+
+type Log = common::Log<(Line, bool)>;
+type Pin = Probe<Line>;
+
+fn chain(bits: u8) -> (Hc595<Pin, Pin, Pin>, Log) {
+    let log = Log::default();
+    (
+        Hc595::new(
+            Probe::new(Line::Data, &log),
+            Probe::new(Line::Clock, &log),
+            Probe::new(Line::Latch, &log),
+            bits,
+        ),
+        log,
+    )
+}
+
+/// The waveform for shifting out `bits`, last output first, then latching
+fn waveform(bits: &[bool]) -> Vec<(Line, bool)> {
+    let mut expected = Vec::new();
+    for &bit in bits.iter().rev() {
+        expected.push((Line::Data, bit));
+        expected.push((Line::Clock, true));
+        expected.push((Line::Clock, false));
+    }
+    expected.push((Line::Latch, true));
+    expected.push((Line::Latch, false));
+    expected
+}
+
+mod hc595 {
+    use super::*;
+
+    #[test]
+    fn shifts_last_output_first_then_latches() {
+        let (chain, log) = chain(4);
+        let port = Port::new(chain);
+        let mut led = port.output(1).into_active_high_switch();
+        led.on().unwrap();
+
+        assert_eq!(waveform(&[false, true, false, false]), take(&log));
+    }
+
+    #[test]
+    fn active_low_output() {
+        let (chain, log) = chain(4);
+        let port = Port::with_outputs(chain, 0b1111);
+        let mut led = port.output(3).into_active_low_switch();
+        led.on().unwrap();
+
+        assert_eq!(waveform(&[true, true, true, false]), take(&log));
+    }
+
+    #[test]
+    fn spans_chained_registers() {
+        let (chain, log) = chain(16);
+        let port = Port::new(chain);
+        let mut led = port.output(9).into_active_high_switch();
+        led.on().unwrap();
+
+        let mut bits = [false; 16];
+        bits[9] = true;
+        assert_eq!(waveform(&bits), take(&log));
+    }
+
+    #[test]
+    fn deferred_latch_shifts_once() {
+        let (chain, log) = chain(4);
+        let port = Port::new(chain);
+        port.set_latch(Latch::Deferred);
+        let mut first = port.output(0).into_active_high_switch();
+        let mut last = port.output(3).into_active_high_switch();
+        first.on().unwrap();
+        last.on().unwrap();
+        assert!(take(&log).is_empty());
+
+        port.flush().unwrap();
+        assert_eq!(waveform(&[true, false, false, true]), take(&log));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 32 bits")]
+    fn chain_longer_than_32_bits_panics() {
+        chain(40);
+    }
+}
+
+/// Simulates a chain of 74HC165 registers
+struct Chain {
+    /// Parallel inputs, indexed like the driver's inputs
+    inputs: u32,
+    registers: u32,
+    /// Bits waiting to be shifted out, serial output first
+    shift: VecDeque<bool>,
+    loading: bool,
+    clock: bool,
+    clocks: usize,
+}
+
+impl Chain {
+    fn new(inputs: u32, registers: u8) -> Self {
+        Chain {
+            inputs,
+            registers: u32::from(registers),
+            shift: VecDeque::new(),
+            loading: false,
+            clock: false,
+            clocks: 0,
+        }
+    }
+
+    /// Serial output, `H` of the first register
+    fn qh(&self) -> bool {
+        self.shift.front().copied().unwrap_or(false)
+    }
+
+    fn load(&mut self) {
+        self.shift.clear();
+        for register in 0..self.registers {
+            for pin in (0..8).rev() {
+                self.shift
+                    .push_back(self.inputs & (1 << (register * 8 + pin)) != 0);
+            }
+        }
+    }
+}
+
+type Sim = Rc<RefCell<Chain>>;
+
+struct Load(Sim);
+struct Clock(Sim);
+struct Data(Sim);
+
+impl OutputSwitch for Load {
+    type Error = &'static str;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        let mut chain = self.0.borrow_mut();
+        chain.loading = true;
+        chain.load();
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().loading = false;
+        Ok(())
+    }
+}
+
+impl OutputSwitch for Clock {
+    type Error = &'static str;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        let mut chain = self.0.borrow_mut();
+        if !chain.clock && !chain.loading {
+            chain.shift.pop_front();
+            chain.clocks += 1;
+        }
+        chain.clock = true;
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().clock = false;
+        Ok(())
+    }
+}
+
+impl InputSwitch for Data {
+    type Error = &'static str;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        Ok(self.0.borrow().qh())
+    }
+}
+
+fn panel(inputs: u32, registers: u8) -> (Port<Hc165<Load, Clock, Data>>, Sim) {
+    let sim = Rc::new(RefCell::new(Chain::new(inputs, registers)));
+    let driver = Hc165::new(
+        Load(sim.clone()),
+        Clock(sim.clone()),
+        Data(sim.clone()),
+        registers,
+    );
+    (Port::new(driver), sim)
+}
+
+mod hc165 {
+    use super::*;
+
+    #[test]
+    fn reads_every_input() {
+        let (panel, _) = panel(0b1000_0101, 1);
+        panel.refresh().unwrap();
+        assert_eq!(0b1000_0101, panel.inputs());
+    }
+
+    #[test]
+    fn reads_chained_registers() {
+        let (panel, _) = panel(0x81_42, 2);
+        panel.refresh().unwrap();
+        assert_eq!(0x81_42, panel.inputs());
+    }
+
+    #[test]
+    fn clocks_once_per_bit() {
+        let (panel, sim) = panel(0, 3);
+        panel.refresh().unwrap();
+        assert_eq!(24, sim.borrow().clocks);
+    }
+
+    #[test]
+    fn handles_interpret_active_level() {
+        let (panel, _) = panel(0b01, 1);
+        let pressed = panel.input(0).into_active_high_switch();
+        let released = panel.input(1).into_active_high_switch();
+        let pulled_up = panel.input(1).into_active_low_switch();
+        panel.refresh().unwrap();
+
+        assert!(pressed.is_active().unwrap());
+        assert!(!released.is_active().unwrap());
+        assert!(pulled_up.is_active().unwrap());
+    }
+
+    #[test]
+    fn handles_read_snapshot_until_refreshed() {
+        let (panel, sim) = panel(0b1, 1);
+        let button = panel.input(0).into_active_high_switch();
+        panel.refresh().unwrap();
+
+        sim.borrow_mut().inputs = 0;
+        assert!(button.is_active().unwrap());
+        assert_eq!(8, sim.borrow().clocks);
+
+        panel.refresh().unwrap();
+        assert!(!button.is_active().unwrap());
+    }
+
+    #[test]
+    fn inverted_serial_output() {
+        let sim = Rc::new(RefCell::new(Chain::new(0b1111_0000, 1)));
+        let qh_inverted = switch_hal::Inverted::new(Data(sim.clone()));
+        let panel = Port::new(Hc165::new(
+            Load(sim.clone()),
+            Clock(sim.clone()),
+            qh_inverted,
+            1,
+        ));
+        panel.refresh().unwrap();
+        assert_eq!(0b0000_1111, panel.inputs());
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 4 registers")]
+    fn chain_longer_than_4_registers_panics() {
+        panel(0, 5);
+    }
+}
@@ -0,0 +1,91 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::sim::Simulator;
+use switch_hal::{
+    ActiveHigh, ActiveLow, InputSwitch, IntoSwitch, OutputSwitch, StatefulOutputSwitch,
+    ToggleableOutputSwitch,
+};
+
+fn render(sim: &Simulator) -> String {
+    let mut panel = Vec::new();
+    sim.render(&mut panel).unwrap();
+    String::from_utf8(panel).unwrap()
+}
+
+mod leds {
+    use super::*;
+
+    #[test]
+    fn start_dark_for_either_wiring() {
+        let mut sim = Simulator::new();
+        let mut high = sim.led::<ActiveHigh>("high");
+        let mut low = sim.led::<ActiveLow>("low");
+
+        assert_eq!("[ ] high\n[ ] low\n", render(&sim));
+        assert_eq!(true, high.is_off().unwrap());
+        assert_eq!(true, low.is_off().unwrap());
+    }
+
+    #[test]
+    fn lit_when_switch_is_on() {
+        let mut sim = Simulator::new();
+        let mut led = sim.led::<ActiveLow>("power");
+        led.on().unwrap();
+
+        assert_eq!(Some(true), sim.is_active("power"));
+        led.toggle().unwrap();
+        assert_eq!(Some(false), sim.is_active("power"));
+        assert_eq!(true, led.is_off().unwrap());
+    }
+
+    #[test]
+    fn miswired_led_shows_inverted() {
+        let mut sim = Simulator::new();
+        let mut led = sim
+            .led::<ActiveLow>("power")
+            .into_pin()
+            .into_active_high_switch();
+        led.on().unwrap();
+
+        assert_eq!("[ ] power\n", render(&sim));
+    }
+}
+
+mod buttons {
+    use super::*;
+
+    #[test]
+    fn key_toggles_pressed() {
+        let mut sim = Simulator::new();
+        let button = sim.button::<ActiveHigh>("start", 's');
+        assert_eq!(false, button.is_active().unwrap());
+
+        assert_eq!(true, sim.handle_key('s'));
+        assert_eq!(true, button.is_active().unwrap());
+        assert_eq!("(s) start: pressed\n", render(&sim));
+
+        sim.handle_key('s');
+        assert_eq!(false, button.is_active().unwrap());
+    }
+
+    #[test]
+    fn active_low_button_pulls_low() {
+        let mut sim = Simulator::new();
+        let button = sim.button::<ActiveLow>("start", 's');
+        sim.handle_key('s');
+
+        assert_eq!(true, button.is_active().unwrap());
+        let pin = button.into_pin();
+        assert_eq!(false, pin.into_active_high_switch().is_active().unwrap());
+    }
+
+    #[test]
+    fn unbound_key_is_ignored() {
+        let mut sim = Simulator::new();
+        sim.button::<ActiveHigh>("start", 's');
+
+        assert_eq!(false, sim.handle_key('x'));
+        assert_eq!(Some(false), sim.is_active("start"));
+        assert_eq!(None, sim.is_active("stop"));
+    }
+}
@@ -0,0 +1,142 @@
+use core::cell::Cell;
+use core::convert::TryFrom;
+
+use embedded_hal::blocking::delay::DelayUs;
+use switch_hal::mock::{MockError, Pin, State};
+use switch_hal::strap::{Error, StrapReader};
+use switch_hal::{bank, ActiveLow, InputSwitch, IntoSwitch, Switch};
+
+/// Reads active on every other read
+struct Floating {
+    reads: Cell<usize>,
+}
+
+impl InputSwitch for Floating {
+    type Error = &'static str;
+
+    fn is_active(&self) -> Result<bool, Self::Error> {
+        let reads = self.reads.get();
+        self.reads.set(reads + 1);
+        Ok(reads % 2 == 1)
+    }
+}
+
+struct Delay {
+    total_us: u32,
+}
+
+impl DelayUs<u32> for Delay {
+    fn delay_us(&mut self, us: u32) {
+        self.total_us += us;
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Address {
+    Primary,
+    Secondary,
+}
+
+impl TryFrom<u8> for Address {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Address::Primary),
+            1 => Ok(Address::Secondary),
+            other => Err(other),
+        }
+    }
+}
+
+fn straps(states: [State; 3]) -> StrapReader<[Switch<Pin, ActiveLow>; 3]> {
+    StrapReader::new(states.map(|s| Pin::with_state(s).into_active_low_switch()))
+}
+
+mod read {
+    use super::*;
+
+    #[test]
+    fn active_straps_set_bits() {
+        let reader = straps([State::Low, State::High, State::Low]);
+        assert_eq!(0b101, reader.read().unwrap());
+    }
+
+    #[test]
+    fn inconsistent_samples_are_an_error() {
+        let reader = StrapReader::new([Floating {
+            reads: Cell::new(0),
+        }]);
+        assert_eq!(
+            Error::Inconsistent { first: 0, other: 1 },
+            reader.read().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn single_sample_cannot_be_inconsistent() {
+        let reader = StrapReader::with_samples(
+            [Floating {
+                reads: Cell::new(0),
+            }],
+            1,
+        );
+        assert_eq!(0, reader.read().unwrap());
+    }
+
+    #[test]
+    fn reports_failing_strap() {
+        let reader = StrapReader::new([
+            Pin::with_state(State::Low).into_active_low_switch(),
+            Pin::new().into_active_low_switch(),
+        ]);
+        assert_eq!(
+            Error::Switch(bank::Error {
+                index: 1,
+                error: MockError::Uninitialized
+            }),
+            reader.read().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn waits_between_samples() {
+        let reader =
+            StrapReader::with_samples([Pin::with_state(State::Low).into_active_low_switch()], 4);
+        let mut delay = Delay { total_us: 0 };
+        assert_eq!(1, reader.read_with_delay(&mut delay, 10).unwrap());
+        assert_eq!(30, delay.total_us);
+    }
+}
+
+mod decode {
+    use super::*;
+
+    #[test]
+    fn decodes_into_integer() {
+        let reader = straps([State::High, State::Low, State::Low]);
+        assert_eq!(6u8, reader.decode::<u8>().unwrap());
+    }
+
+    #[test]
+    fn decodes_into_enum() {
+        let reader = straps([State::Low, State::High, State::High]);
+        assert_eq!(Address::Secondary, reader.decode::<Address>().unwrap());
+    }
+
+    #[test]
+    fn reports_invalid_value() {
+        let reader = straps([State::Low, State::Low, State::High]);
+        assert_eq!(Error::Decode(3), reader.decode::<Address>().unwrap_err());
+    }
+}
+
+mod size {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "at most 8 straps")]
+    fn more_than_8_straps_panics() {
+        StrapReader::new([(); 9].map(|_| Pin::new().into_active_low_switch()));
+    }
+}
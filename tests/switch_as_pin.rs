@@ -0,0 +1,135 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use embedded_hal::digital::v2::{InputPin, OutputPin, ToggleableOutputPin};
+use switch_hal::mock::{Pin, State};
+use switch_hal::{
+    IntoSwitch, Invert, OutputSwitch, SwitchPin, SwitchState, ToggleableOutputSwitch,
+};
+
+/// Stands in for a third party driver that asserts chip select by driving it high
+struct Device<CS> {
+    chip_select: CS,
+}
+
+impl<CS: OutputPin> Device<CS> {
+    fn new(chip_select: CS) -> Self {
+        Device { chip_select }
+    }
+
+    fn select(&mut self) -> Result<(), CS::Error> {
+        self.chip_select.set_high()
+    }
+
+    fn deselect(&mut self) -> Result<(), CS::Error> {
+        self.chip_select.set_low()
+    }
+
+    fn release(self) -> CS {
+        self.chip_select
+    }
+}
+
+mod switch {
+    use super::*;
+
+    #[test]
+    fn switch_toggle_is_unambiguous_with_pin_traits_in_scope() {
+        let mut led = Pin::with_state(State::Low).into_active_high_switch();
+        led.toggle().unwrap();
+
+        assert_eq!(true, led.into_pin().is_high().unwrap());
+    }
+
+    #[test]
+    fn switch_set_state_is_unambiguous_with_pin_traits_in_scope() {
+        let mut led = Pin::new().into_active_low_switch();
+        led.set_state(SwitchState::On).unwrap();
+
+        assert_eq!(true, led.into_pin().is_low().unwrap());
+    }
+
+    #[test]
+    fn active_low_input_reads_high_when_active() {
+        let button = Pin::with_state(State::Low).into_active_low_switch();
+        assert_eq!(true, button.is_high().unwrap());
+        assert_eq!(false, button.is_low().unwrap());
+    }
+}
+
+mod switch_pin {
+    use super::*;
+
+    #[test]
+    fn active_low_chip_select_is_driven_low_when_selected() {
+        let chip_select = Pin::new().into_active_low_switch();
+        let mut device = Device::new(SwitchPin::new(chip_select));
+        device.select().unwrap();
+
+        let pin = device.release().into_inner().into_pin();
+        assert_eq!(true, pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn active_low_chip_select_is_driven_high_when_deselected() {
+        let chip_select = Pin::new().into_active_low_switch();
+        let mut device = Device::new(SwitchPin::new(chip_select));
+        device.deselect().unwrap();
+
+        let pin = device.release().into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn active_high_output_follows_pin() {
+        let chip_select = Pin::new().into_active_high_switch();
+        let mut device = Device::new(SwitchPin::new(chip_select));
+        device.select().unwrap();
+
+        let pin = device.release().into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn is_toggleable() {
+        let mut chip_select = SwitchPin::new(Pin::with_state(State::High).into_active_low_switch());
+        chip_select.toggle().unwrap();
+
+        let pin = chip_select.into_inner().into_pin();
+        assert_eq!(true, pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn set_high_turns_switch_on() {
+        let chip_select = Invert::inverted(Pin::new().into_active_high_switch());
+        let mut device = Device::new(SwitchPin::new(chip_select));
+        device.select().unwrap();
+
+        let pin = device.release().into_inner().into_inner().into_pin();
+        assert_eq!(true, pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn set_low_turns_switch_off() {
+        let chip_select = Invert::inverted(Pin::new().into_active_high_switch());
+        let mut device = Device::new(SwitchPin::new(chip_select));
+        device.deselect().unwrap();
+
+        let pin = device.release().into_inner().into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn reads_high_when_active() {
+        let button = SwitchPin::new(Invert::inverted(
+            Pin::with_state(State::Low).into_active_high_switch(),
+        ));
+        assert_eq!(true, button.is_high().unwrap());
+        assert_eq!(false, button.is_low().unwrap());
+    }
+
+    #[test]
+    fn propagates_errors_from_pin() {
+        let button = SwitchPin::new(Invert::inverted(Pin::new().into_active_high_switch()));
+        button.is_high().expect_err("Expected uninitialized error");
+    }
+}
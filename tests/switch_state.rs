@@ -0,0 +1,171 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::mock::{Pin, State};
+use switch_hal::{IntoSwitch, SwitchState};
+
+mod switch_state {
+    use super::*;
+
+    #[test]
+    fn from_bool() {
+        assert_eq!(SwitchState::On, SwitchState::from(true));
+        assert_eq!(SwitchState::Off, SwitchState::from(false));
+    }
+
+    #[test]
+    fn into_bool() {
+        assert_eq!(true, bool::from(SwitchState::On));
+        assert_eq!(false, bool::from(SwitchState::Off));
+    }
+
+    #[test]
+    fn not() {
+        assert_eq!(SwitchState::Off, !SwitchState::On);
+        assert_eq!(SwitchState::On, !SwitchState::Off);
+    }
+}
+
+mod input_switch {
+    use super::*;
+    use switch_hal::InputSwitch;
+
+    #[test]
+    fn on_when_active() {
+        let button = Pin::with_state(State::Low).into_active_low_switch();
+        assert_eq!(SwitchState::On, button.state().unwrap());
+    }
+
+    #[test]
+    fn off_when_inactive() {
+        let button = Pin::with_state(State::High).into_active_low_switch();
+        assert_eq!(SwitchState::Off, button.state().unwrap());
+    }
+
+    #[test]
+    fn propagates_errors_from_pin() {
+        let button = Pin::new().into_active_high_switch();
+        button.state().expect_err("Expected uninitialized error");
+    }
+}
+
+mod output_switch {
+    use super::*;
+    use embedded_hal::digital::v2::InputPin;
+    use switch_hal::{OutputSwitch, StatefulOutputSwitch};
+
+    #[test]
+    fn set_state_on() {
+        let mut led = Pin::new().into_active_low_switch();
+        led.set_state(SwitchState::On).unwrap();
+
+        let pin = led.into_pin();
+        assert_eq!(true, pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn set_state_off() {
+        let mut led = Pin::new().into_active_low_switch();
+        led.set_state(SwitchState::Off).unwrap();
+
+        let pin = led.into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let mut led = Pin::new().into_active_high_switch();
+
+        led.set_state(SwitchState::On).unwrap();
+        assert_eq!(
+            SwitchState::On,
+            StatefulOutputSwitch::state(&mut led).unwrap()
+        );
+
+        led.set_state(SwitchState::Off).unwrap();
+        assert_eq!(
+            SwitchState::Off,
+            StatefulOutputSwitch::state(&mut led).unwrap()
+        );
+    }
+}
+
+mod references {
+    use super::*;
+    use switch_hal::{InputSwitch, OutputSwitch, StatefulOutputSwitch};
+
+    /// Overrides every provided method, so forwarding through a reference is observable
+    struct Overridden;
+
+    impl InputSwitch for Overridden {
+        type Error = &'static str;
+
+        fn is_active(&self) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+
+        fn state(&self) -> Result<SwitchState, Self::Error> {
+            Err("overridden")
+        }
+    }
+
+    impl OutputSwitch for Overridden {
+        type Error = &'static str;
+
+        fn on(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn off(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_state(&mut self, _state: SwitchState) -> Result<(), Self::Error> {
+            Err("overridden")
+        }
+    }
+
+    impl StatefulOutputSwitch for Overridden {
+        type Error = &'static str;
+
+        fn is_on(&mut self) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+
+        fn is_off(&mut self) -> Result<bool, Self::Error> {
+            Ok(false)
+        }
+
+        fn state(&mut self) -> Result<SwitchState, Self::Error> {
+            Err("overridden")
+        }
+    }
+
+    fn input_state<S: InputSwitch>(switch: S) -> Result<SwitchState, S::Error> {
+        switch.state()
+    }
+
+    fn set_on<S: OutputSwitch>(mut switch: S) -> Result<(), S::Error> {
+        switch.set_state(SwitchState::On)
+    }
+
+    fn output_state<S: StatefulOutputSwitch>(mut switch: S) -> Result<SwitchState, S::Error> {
+        switch.state()
+    }
+
+    #[test]
+    fn forward_input_state() {
+        let mut switch = Overridden;
+        assert_eq!(Err("overridden"), input_state(&switch));
+        assert_eq!(Err("overridden"), input_state(&mut switch));
+    }
+
+    #[test]
+    fn forward_set_state() {
+        assert_eq!(Err("overridden"), set_on(&mut Overridden));
+    }
+
+    #[test]
+    fn forward_stateful_state() {
+        assert_eq!(Err("overridden"), output_state(&mut Overridden));
+    }
+}
@@ -0,0 +1,256 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use embedded_hal::digital::v2::InputPin;
+use switch_hal::mock::Pin;
+use switch_hal::tone::{Melody, Note, SoftToggle, Tone, ToneGenerator};
+use switch_hal::{IntoSwitch, OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch};
+
+/// Counts how many times it was toggled
+#[derive(Default)]
+struct Buzzer {
+    on: bool,
+    toggles: usize,
+}
+
+impl OutputSwitch for Buzzer {
+    type Error = &'static str;
+
+    fn on(&mut self) -> Result<(), Self::Error> {
+        self.on = true;
+        Ok(())
+    }
+
+    fn off(&mut self) -> Result<(), Self::Error> {
+        self.on = false;
+        Ok(())
+    }
+}
+
+impl ToggleableOutputSwitch for Buzzer {
+    type Error = &'static str;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.on = !self.on;
+        self.toggles += 1;
+        Ok(())
+    }
+}
+
+/// Records the tones it was asked to play
+#[derive(Default)]
+struct Recorder {
+    playing: Rc<Cell<Option<u32>>>,
+    started: Vec<u32>,
+    ticks: usize,
+}
+
+impl ToneGenerator for Recorder {
+    type Error = &'static str;
+
+    fn start(&mut self, frequency_hz: u32) -> Result<(), Self::Error> {
+        self.started.push(frequency_hz);
+        self.playing.set(if frequency_hz == 0 {
+            None
+        } else {
+            Some(frequency_hz)
+        });
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Self::Error> {
+        self.playing.set(None);
+        Ok(())
+    }
+
+    fn tick(&mut self) -> Result<(), Self::Error> {
+        self.ticks += 1;
+        Ok(())
+    }
+}
+
+mod tone {
+    use super::*;
+
+    #[test]
+    fn toggles_at_twice_the_frequency() {
+        let mut tone = Tone::new(Buzzer::default(), 8_000);
+        tone.start(1_000).unwrap();
+        for _ in 0..8_000 {
+            tone.tick().unwrap();
+        }
+
+        assert_eq!(2_000, tone.into_inner().toggles);
+    }
+
+    #[test]
+    fn silent_until_started() {
+        let mut tone = Tone::new(Buzzer::default(), 8_000);
+        for _ in 0..100 {
+            tone.tick().unwrap();
+        }
+
+        assert_eq!(false, tone.is_playing());
+        assert_eq!(0, tone.into_inner().toggles);
+    }
+
+    #[test]
+    fn stop_turns_switch_off() {
+        let mut tone = Tone::new(Buzzer::default(), 8_000);
+        tone.start(4_000).unwrap();
+        tone.tick().unwrap();
+        tone.stop().unwrap();
+        tone.tick().unwrap();
+
+        let buzzer = tone.into_inner();
+        assert_eq!(false, buzzer.on);
+        assert_eq!(1, buzzer.toggles);
+    }
+
+    #[test]
+    fn zero_frequency_stops() {
+        let mut tone = Tone::new(Buzzer::default(), 8_000);
+        tone.start(1_000).unwrap();
+        tone.start(0).unwrap();
+        assert_eq!(false, tone.is_playing());
+    }
+
+    #[test]
+    fn frequency_above_nyquist_toggles_every_tick() {
+        let mut tone = Tone::new(Buzzer::default(), 1_000);
+        tone.start(10_000).unwrap();
+        for _ in 0..10 {
+            tone.tick().unwrap();
+        }
+
+        assert_eq!(10, tone.into_inner().toggles);
+    }
+
+    #[test]
+    fn drives_switch() {
+        let mut tone = Tone::new(Pin::new().into_active_low_switch(), 2);
+        tone.start(1).unwrap();
+        tone.stop().unwrap();
+
+        let pin = tone.into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+}
+
+mod soft_toggle {
+    use super::*;
+
+    #[test]
+    fn toggles_from_off() {
+        let mut buzzer = SoftToggle::new(Pin::new().into_active_high_switch());
+        buzzer.toggle().unwrap();
+
+        assert_eq!(true, buzzer.is_on().unwrap());
+        let pin = buzzer.into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+
+    #[test]
+    fn toggles_back_off() {
+        let mut buzzer = SoftToggle::new(Pin::new().into_active_high_switch());
+        buzzer.on().unwrap();
+        buzzer.toggle().unwrap();
+
+        assert_eq!(true, buzzer.is_off().unwrap());
+        let pin = buzzer.into_inner().into_pin();
+        assert_eq!(true, pin.is_low().unwrap());
+    }
+
+    #[test]
+    fn usable_by_tone() {
+        let mut tone = Tone::new(SoftToggle::new(Pin::new().into_active_high_switch()), 2);
+        tone.start(1).unwrap();
+        tone.tick().unwrap();
+
+        let pin = tone.into_inner().into_inner().into_pin();
+        assert_eq!(true, pin.is_high().unwrap());
+    }
+}
+
+mod melody {
+    use super::*;
+
+    static NOTES: [Note; 3] = [Note::new(440, 2), Note::rest(1), Note::new(880, 3)];
+
+    #[test]
+    fn plays_each_note_for_its_duration() {
+        let playing = Rc::new(Cell::new(None));
+        let recorder = Recorder {
+            playing: playing.clone(),
+            ..Recorder::default()
+        };
+        let mut melody = Melody::new(recorder, &NOTES, 1_000);
+
+        let mut heard = Vec::new();
+        while !melody.is_finished() {
+            melody.tick().unwrap();
+            heard.push(playing.get());
+        }
+
+        assert_eq!(
+            vec![
+                Some(440),
+                Some(440),
+                None,
+                Some(880),
+                Some(880),
+                Some(880),
+                None
+            ],
+            heard
+        );
+    }
+
+    #[test]
+    fn ticks_generator_while_playing() {
+        let mut melody = Melody::new(Recorder::default(), &NOTES, 1_000);
+        while !melody.is_finished() {
+            melody.tick().unwrap();
+        }
+
+        assert_eq!(6, melody.into_inner().ticks);
+    }
+
+    #[test]
+    fn skips_notes_shorter_than_a_tick() {
+        static SHORT: [Note; 2] = [Note::new(440, 0), Note::new(880, 1)];
+        let mut melody = Melody::new(Recorder::default(), &SHORT, 1_000);
+        melody.tick().unwrap();
+
+        assert_eq!(vec![880], melody.into_inner().started);
+    }
+
+    #[test]
+    fn restart() {
+        let mut melody = Melody::new(Recorder::default(), &NOTES, 1_000);
+        while !melody.is_finished() {
+            melody.tick().unwrap();
+        }
+        melody.restart();
+        melody.tick().unwrap();
+
+        assert_eq!(false, melody.is_finished());
+        assert_eq!(vec![440, 0, 880, 440], melody.into_inner().started);
+    }
+
+    #[test]
+    fn plays_on_tone() {
+        let tone = Tone::new(Buzzer::default(), 8_000);
+        let mut melody = Melody::new(tone, &NOTES, 8_000);
+        while !melody.is_finished() {
+            melody.tick().unwrap();
+        }
+
+        let buzzer = melody.into_inner().into_inner();
+        assert_eq!(false, buzzer.on);
+        // 2ms at 440Hz and 3ms at 880Hz, toggling every 9 and 4 ticks
+        assert_eq!(16 / 9 + 24 / 4, buzzer.toggles);
+    }
+}
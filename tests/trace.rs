@@ -0,0 +1,80 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use switch_hal::mock::{MockClock, Pin, State};
+use switch_hal::trace::Tracer;
+use switch_hal::{
+    InputSwitch, IntoSwitch, OutputSwitch, StatefulOutputSwitch, ToggleableOutputSwitch,
+};
+
+#[test]
+fn matches_golden_file() {
+    let clock = MockClock::new();
+    let tracer = Tracer::new(&clock);
+    let mut relay = tracer.trace("relay", Pin::new().into_active_high_switch());
+    let mut led = tracer.trace("status led", Pin::new().into_active_low_switch());
+    let button = tracer.trace(
+        "button",
+        Pin::with_state(State::High).into_active_low_switch(),
+    );
+
+    relay.off().unwrap();
+    button.is_active().unwrap();
+    clock.advance(10);
+    led.on().unwrap();
+    relay.on().unwrap();
+    clock.advance(5);
+    led.toggle().unwrap();
+    led.toggle().unwrap();
+    button.is_active().unwrap();
+
+    assert_eq!(include_str!("golden/trace.vcd"), tracer.to_vcd());
+}
+
+#[test]
+fn unchanged_states_are_not_recorded() {
+    let clock = MockClock::new();
+    let tracer = Tracer::new(&clock);
+    let mut led = tracer.trace("led", Pin::new().into_active_high_switch());
+    led.on().unwrap();
+    clock.advance(1);
+    led.on().unwrap();
+    assert_eq!(true, led.is_on().unwrap());
+
+    assert_eq!(false, tracer.to_vcd().contains("#1"));
+}
+
+#[test]
+fn toggle_of_unknown_state_is_recorded_once_read() {
+    let clock = MockClock::new();
+    let tracer = Tracer::new(&clock);
+    let mut led = tracer.trace("led", Pin::with_state(State::Low).into_active_high_switch());
+    led.toggle().unwrap();
+    led.is_on().unwrap();
+
+    assert_eq!(true, tracer.to_vcd().ends_with("$end\n#0\n1!\n"));
+}
+
+#[test]
+fn errors_are_not_recorded() {
+    let clock = MockClock::new();
+    let tracer = Tracer::new(&clock);
+    let button = tracer.trace("button", Pin::new().into_active_high_switch());
+    button.is_active().expect_err("Expected uninitialized pin");
+
+    assert_eq!(true, tracer.to_vcd().ends_with("$dumpvars\nx!\n$end\n"));
+}
+
+#[test]
+fn identifiers_are_unique_past_printable_range() {
+    let clock = MockClock::new();
+    let tracer = Tracer::new(&clock);
+    let switches: Vec<_> = (0..100)
+        .map(|i| tracer.trace(&format!("led{}", i), Pin::new().into_active_high_switch()))
+        .collect();
+    drop(switches);
+
+    let vcd = tracer.to_vcd();
+    assert_eq!(true, vcd.contains("$var wire 1 ~ led93 $end"));
+    assert_eq!(true, vcd.contains("$var wire 1 !! led94 $end"));
+    assert_eq!(true, vcd.contains("$var wire 1 &! led99 $end"));
+}